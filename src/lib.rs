@@ -1,7 +1,26 @@
+#[cfg(feature = "flate2")]
+extern crate flate2;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+#[cfg(feature = "flate2")]
+use flate2::read::GzDecoder;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::mem;
+use std::num::FpCategory;
 
 type GetResult<T> = Result<T, GetError>;
 
@@ -9,28 +28,177 @@ type GetResult<T> = Result<T, GetError>;
 pub enum GetError {
     BitGet(BitGetError),
     ChecksumMismatch { expected: u32, actual: u32 },
+    EmptySection,
     IndexOutOfBounds { index: usize, len: usize },
-    InvalidUtf16(Vec<u8>),
     InvalidWindows1252(Vec<u8>),
+    Io(io::Error),
+    KeyframeOutOfRange { frame: u32, offset: u32 },
+    SizeOutOfRange(i64),
+    TextTooLarge { size: i32 },
     UnknownProperty(String),
+    Utf16InvalidUnits(Vec<u8>),
+    Utf16OddLength(Vec<u8>),
 }
 
-pub struct Get {
-    bytes: Vec<u8>,
+impl fmt::Display for GetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GetError::BitGet(problem) => write!(f, "failed to decode network data: {}", problem),
+            GetError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch: expected {:#010x} but got {:#010x}",
+                expected, actual
+            ),
+            GetError::EmptySection => write!(f, "section is empty"),
+            GetError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for length {}", index, len)
+            }
+            GetError::InvalidWindows1252(bytes) => {
+                write!(f, "invalid Windows-1252 text: {:?}", bytes)
+            }
+            GetError::Io(problem) => write!(f, "failed to read: {}", problem),
+            GetError::KeyframeOutOfRange { frame, offset } => write!(
+                f,
+                "keyframe for frame {} at bit {} is out of range",
+                frame, offset
+            ),
+            GetError::SizeOutOfRange(size) => write!(f, "size {} is out of range", size),
+            GetError::TextTooLarge { size } => {
+                write!(f, "text size {} is larger than the remaining input", size)
+            }
+            GetError::UnknownProperty(label) => {
+                write!(f, "unknown property {:?}", label.trim_end_matches('\0'))
+            }
+            GetError::Utf16InvalidUnits(bytes) => {
+                write!(f, "invalid UTF-16 code units: {:?}", bytes)
+            }
+            GetError::Utf16OddLength(bytes) => {
+                write!(f, "UTF-16 text has an odd length: {:?}", bytes)
+            }
+        }
+    }
+}
+
+impl From<BitGetError> for GetError {
+    fn from(problem: BitGetError) -> Self {
+        GetError::BitGet(problem)
+    }
+}
+
+impl Error for GetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GetError::BitGet(problem) => Some(problem),
+            GetError::Io(problem) => Some(problem),
+            _ => None,
+        }
+    }
+}
+
+pub struct Get<'a> {
+    bytes: Cow<'a, [u8]>,
     index: usize,
+    options: Options,
 }
 
-impl Get {
+impl Get<'static> {
     pub fn new(bytes: Vec<u8>) -> Self {
-        Self { bytes, index: 0 }
+        Self {
+            bytes: Cow::Owned(bytes),
+            index: 0,
+            options: Options::new(),
+        }
+    }
+}
+
+impl<'a> Get<'a> {
+    pub fn from_slice(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes: Cow::Borrowed(bytes),
+            index: 0,
+            options: Options::new(),
+        }
+    }
+
+    // Returns the previous buffer so that it can be filled with the next
+    // replay, unless it was borrowed.
+    pub fn reset(&mut self, bytes: Vec<u8>) -> Option<Vec<u8>> {
+        self.index = 0;
+        match mem::replace(&mut self.bytes, Cow::Owned(bytes)) {
+            Cow::Borrowed(_) => None,
+            Cow::Owned(bytes) => Some(bytes),
+        }
+    }
+
+    pub fn set_float_policy(&mut self, float_policy: FloatPolicy) {
+        self.options.float_policy = float_policy
     }
+
+    pub fn set_max_frames(&mut self, max_frames: usize) {
+        self.options.max_frames = max_frames
+    }
+
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.options.lenient = lenient
+    }
+
+    pub fn set_attribute_decoder(&mut self, name: &str, decoder: AttributeDecoder) {
+        let name = format!("{}\0", name.trim_end_matches('\0'));
+        self.options.decoders.insert(name, decoder);
+    }
+}
+
+#[derive(Clone)]
+struct Options {
+    float_policy: FloatPolicy,
+    max_frames: usize,
+    lenient: bool,
+    decoders: HashMap<String, AttributeDecoder>,
+}
+
+impl Options {
+    fn new() -> Self {
+        Self {
+            float_policy: FloatPolicy::Allow,
+            max_frames: 1_000_000,
+            lenient: false,
+            decoders: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FloatPolicy {
+    Allow,
+    Reject,
+    Zero,
 }
 
 type BitGetResult<T> = Result<T, BitGetError>;
 
 #[derive(Debug)]
 pub enum BitGetError {
-    IndexOutOfBounds { index: usize, len: usize },
+    FrameCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    IndexOutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    InvalidFloat(u32),
+    InvalidUtf16(Vec<u8>),
+    InvalidWindows1252(Vec<u8>),
+    Located {
+        position: Position,
+        problem: Box<BitGetError>,
+    },
+    SizeOutOfRange(i64),
+    TooManyBits(u32),
+    TooManyFrames {
+        num_frames: usize,
+        max_frames: usize,
+    },
     UnknownActor(u32),
     UnknownAttribute(String),
     UnknownAttributeIndex(u32),
@@ -38,9 +206,102 @@ pub enum BitGetError {
     UnknownName(u32),
     UnknownObject(u32),
     UnknownObjectClass(String),
+    UnknownPlatform(u8),
+    UnknownProduct(String),
     UnknownStreamId(u32),
 }
 
+impl fmt::Display for BitGetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BitGetError::FrameCountMismatch { expected, actual } => write!(
+                f,
+                "network data ran out after decoding {} of {} frames",
+                actual, expected
+            ),
+            BitGetError::IndexOutOfBounds { index, len } => {
+                write!(f, "byte {} is out of bounds for length {}", index, len)
+            }
+            BitGetError::InvalidFloat(bits) => write!(f, "invalid float {:#010x}", bits),
+            BitGetError::InvalidUtf16(bytes) => write!(f, "invalid UTF-16 text: {:?}", bytes),
+            BitGetError::InvalidWindows1252(bytes) => {
+                write!(f, "invalid Windows-1252 text: {:?}", bytes)
+            }
+            BitGetError::Located { position, problem } => {
+                write!(f, "{} in frame {}", problem, position.frame)?;
+                if let Some(actor) = position.actor {
+                    write!(f, " for actor {}", actor)?;
+                }
+                write!(
+                    f,
+                    " at byte {} bit {}",
+                    position.byte_index, position.bit_index
+                )
+            }
+            BitGetError::SizeOutOfRange(size) => write!(f, "size {} is out of range", size),
+            BitGetError::TooManyBits(count) => {
+                write!(f, "cannot read {} bits into 32 bits", count)
+            }
+            BitGetError::TooManyFrames {
+                num_frames,
+                max_frames,
+            } => write!(
+                f,
+                "replay has {} frames but at most {} are allowed",
+                num_frames, max_frames
+            ),
+            BitGetError::UnknownActor(actor) => write!(f, "unknown actor {}", actor),
+            BitGetError::UnknownAttribute(name) => {
+                write!(f, "unknown attribute {:?}", name.trim_end_matches('\0'))
+            }
+            BitGetError::UnknownAttributeIndex(index) => {
+                write!(f, "unknown attribute index {}", index)
+            }
+            BitGetError::UnknownClass(class) => write!(f, "unknown class {}", class),
+            BitGetError::UnknownName(name) => write!(f, "unknown name {}", name),
+            BitGetError::UnknownObject(object) => write!(f, "unknown object {}", object),
+            BitGetError::UnknownObjectClass(object) => write!(
+                f,
+                "unknown class for object {:?}",
+                object.trim_end_matches('\0')
+            ),
+            BitGetError::UnknownPlatform(platform) => write!(f, "unknown platform {}", platform),
+            BitGetError::UnknownProduct(product) => {
+                write!(f, "unknown product {:?}", product.trim_end_matches('\0'))
+            }
+            BitGetError::UnknownStreamId(stream_id) => {
+                write!(f, "unknown stream id {}", stream_id)
+            }
+        }
+    }
+}
+
+impl BitGetError {
+    fn root(&self) -> &BitGetError {
+        match self {
+            BitGetError::Located { problem, .. } => problem.root(),
+            _ => self,
+        }
+    }
+}
+
+impl Error for BitGetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BitGetError::Located { problem, .. } => Some(problem.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub byte_index: usize,
+    pub bit_index: usize,
+    pub frame: usize,
+    pub actor: Option<u32>,
+}
+
 struct BitGet {
     bytes: Vec<u8>,
     byte_index: usize,
@@ -57,42 +318,243 @@ impl BitGet {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Replay {
     header: Section<Header>,
     content: Section<Content>,
+    debug: Option<Dictionary<Property>>,
 }
 
-impl Get {
+impl Get<'_> {
     pub fn get_replay(&mut self) -> GetResult<Replay> {
         let header = self.get_section(Self::get_header)?;
         let content = self.get_section(|this| this.get_content(&header.value))?;
-        Ok(Replay { header, content })
+        let debug = self.get_debug();
+        Ok(Replay {
+            header,
+            content,
+            debug,
+        })
+    }
+
+    pub fn get_replay_resumable(&mut self) -> GetResult<(Replay, FrameDecoder)> {
+        let header = self.get_section(Self::get_header)?;
+        let Section { size, crc, value } =
+            self.get_section(|this| this.get_content_without_frames(&header.value))?;
+        let (content, decoder) = value;
+        let debug = self.get_debug();
+        let replay = Replay {
+            header,
+            content: Section {
+                size,
+                crc,
+                value: content,
+            },
+            debug,
+        };
+        Ok((replay, decoder))
+    }
+
+    // Some replays have a dictionary of debug properties after the content,
+    // but other trailing bytes are not an error.
+    fn get_debug(&mut self) -> Option<Dictionary<Property>> {
+        if self.index >= self.bytes.len() {
+            return None;
+        }
+        let index = self.index;
+        match self.get_dictionary(Self::get_property) {
+            Err(_) => {
+                self.index = index;
+                None
+            }
+            Ok(debug) => Some(debug),
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct HeaderOnly {
+    header: Section<Header>,
+    content: Section<Content>,
+}
+
+impl Get<'_> {
+    pub fn get_header_only(&mut self) -> GetResult<HeaderOnly> {
+        let header = self.get_section(Self::get_header)?;
+        let Section { size, crc, value } = self.get_section(Self::get_content_lists)?;
+        let (content, _) = value;
+        Ok(HeaderOnly {
+            header,
+            content: Section {
+                size,
+                crc,
+                value: content,
+            },
+        })
+    }
+}
+
+impl HeaderOnly {
+    pub fn header(&self) -> &Header {
+        &self.header.value
+    }
+
+    pub fn content(&self) -> &Content {
+        &self.content.value
+    }
+}
+
+pub fn parse(bytes: &[u8]) -> GetResult<Replay> {
+    #[cfg(feature = "flate2")]
+    {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            return parse_gzip(bytes);
+        }
+    }
+    Get::from_slice(bytes).get_replay()
+}
+
+// The format is not seekable, so the whole input is buffered first.
+pub fn parse_reader<R: Read>(mut reader: R) -> GetResult<Replay> {
+    let mut bytes = Vec::new();
+    match reader.read_to_end(&mut bytes) {
+        Err(problem) => Err(GetError::Io(problem)),
+        Ok(_) => Get::new(bytes).get_replay(),
+    }
+}
+
+pub fn parse_header(bytes: &[u8]) -> GetResult<HeaderOnly> {
+    Get::from_slice(bytes).get_header_only()
+}
+
+#[cfg(feature = "flate2")]
+pub fn parse_gzip(bytes: &[u8]) -> GetResult<Replay> {
+    let mut decoded = Vec::new();
+    match GzDecoder::new(bytes).read_to_end(&mut decoded) {
+        Err(problem) => Err(GetError::Io(problem)),
+        Ok(_) => Get::new(decoded).get_replay(),
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ReplayFeatures {
+    pub has_frames: bool,
+    pub has_mutators: bool,
+    pub is_private: bool,
+    pub num_players: usize,
+    pub platforms: Vec<String>,
+}
+
+impl Replay {
+    pub fn header(&self) -> &Header {
+        &self.header.value
+    }
+
+    pub fn content(&self) -> &Content {
+        &self.content.value
+    }
+
+    pub fn debug(&self) -> Option<&Dictionary<Property>> {
+        self.debug.as_ref()
+    }
+
+    pub fn frames(&self) -> impl Iterator<Item = &Frame> {
+        self.content().frames()
+    }
+
+    pub fn validate_keyframes(&self) -> GetResult<()> {
+        let num_frames = self.header().property_int("NumFrames");
+        self.content().validate_keyframes(num_frames)
+    }
+
+    pub fn map_name(&self) -> Option<&str> {
+        self.header().property_str("MapName")
+    }
+
+    pub fn pretty_map_name(&self) -> Option<&'static str> {
+        self.map_name().and_then(pretty_map_name)
+    }
+
+    pub fn features(&self) -> ReplayFeatures {
+        let header = self.header();
+        let players: &[Dictionary<Property>] = match header.find_property("PlayerStats\0") {
+            Some(PropertyValue::Array(players)) => &players.value,
+            _ => &[],
+        };
+        let mut platforms = Vec::new();
+        for player in players {
+            if let Some(Property {
+                value: PropertyValue::Byte { key, value },
+                ..
+            }) = player.get("Platform")
+            {
+                let platform = value.as_ref().unwrap_or(key);
+                let platform = platform.value.trim_end_matches('\0').to_string();
+                if !platforms.contains(&platform) {
+                    platforms.push(platform)
+                }
+            }
+        }
+        ReplayFeatures {
+            has_frames: !self.content().frames.is_empty(),
+            has_mutators: !self.content().active_mutators().is_empty(),
+            is_private: match header.find_property("MatchType\0") {
+                Some(PropertyValue::Name(x)) => x.value.as_str() == "Private\0",
+                _ => false,
+            },
+            num_players: players.len(),
+            platforms,
+        }
+    }
+
+    // The header has no property that marks a forfeit, so this relies on the
+    // game event replicating bForfeit when a team votes to forfeit.
+    pub fn was_forfeit(&self) -> bool {
+        self.content()
+            .attribute_updates()
+            .any(|(_, _, _, attribute)| {
+                attribute.object.as_str() == "TAGame.GameEvent_Team_TA:bForfeit\0"
+                    && match attribute.value {
+                        AttributeValue::Boolean(x) => x,
+                        _ => false,
+                    }
+            })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 struct Section<T> {
     size: u32,
     crc: u32,
     value: T,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_section<F, T>(&mut self, get_value: F) -> GetResult<Section<T>>
     where
         F: Fn(&mut Self) -> GetResult<T>,
     {
         let size = self.get_u32()?;
+        if size == 0 {
+            return Err(GetError::EmptySection);
+        }
         let crc = self.get_u32()?;
         self.check_crc_32(size, crc)?;
+        let end = self.index + get_size(size)?;
         let value = get_value(self)?;
+        if self.index < end {
+            self.index = end;
+        }
         Ok(Section { size, crc, value })
     }
 
     fn check_crc_32(&self, size: u32, expected: u32) -> GetResult<()> {
-        let bytes = self.peek_vec(u32_usize(size))?;
-        let actual = crc_32(&bytes);
+        let bytes = self.peek_slice(get_size(size)?)?;
+        let actual = crc_32(bytes);
         if actual == expected {
             Ok(())
         } else {
@@ -101,14 +563,15 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
-struct Header {
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Header {
     version: Version,
     label: Text,
     properties: Dictionary<Property>,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_header(&mut self) -> GetResult<Header> {
         let version = self.get_version()?;
         let label = self.get_text()?;
@@ -121,14 +584,188 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
-struct Version {
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Goal {
+    pub frame: u32,
+    pub player: String,
+    pub team: u8,
+}
+
+impl Header {
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn label(&self) -> &str {
+        self.label.as_str()
+    }
+
+    pub fn properties(&self) -> &Dictionary<Property> {
+        &self.properties
+    }
+
+    pub fn goals(&self) -> Vec<Goal> {
+        let goals = match self.find_property("Goals\0") {
+            Some(PropertyValue::Array(goals)) => &goals.value,
+            _ => return Vec::new(),
+        };
+        goals
+            .iter()
+            .map(|goal| {
+                let mut frame = 0;
+                let mut player = String::new();
+                let mut team = 0;
+                for (key, property) in &goal.value {
+                    match (key.value.as_str(), &property.value) {
+                        ("frame\0", PropertyValue::Int(x)) => frame = *x,
+                        ("PlayerName\0", PropertyValue::Str(x)) => {
+                            player = x.value.trim_end_matches('\0').to_string()
+                        }
+                        ("PlayerTeam\0", PropertyValue::Int(x)) => {
+                            team = u32_u8(*x).unwrap_or(team)
+                        }
+                        _ => (),
+                    }
+                }
+                Goal {
+                    frame,
+                    player,
+                    team,
+                }
+            })
+            .collect()
+    }
+
+    // Replays do not record the playlist id in the header. Only playlists
+    // that can be told apart by the match type or the map are recognized.
+    pub fn playlist(&self) -> Playlist {
+        if let Some(PropertyValue::Name(x)) = self.find_property("MatchType\0") {
+            if x.value.as_str() == "Private\0" {
+                return Playlist::Private;
+            }
+        }
+        let map = self.property_str("MapName").unwrap_or("");
+        if map.starts_with("HoopsStadium") || map.starts_with("HoopsStreet") {
+            Playlist::Hoops
+        } else if map.starts_with("ShatterShot") {
+            Playlist::Dropshot
+        } else {
+            Playlist::Unknown(None)
+        }
+    }
+
+    pub fn property(&self, name: &str) -> Option<&PropertyValue> {
+        self.find_property(name)
+    }
+
+    pub fn property_int(&self, name: &str) -> Option<u32> {
+        match self.property(name) {
+            Some(PropertyValue::Int(x)) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn property_str(&self, name: &str) -> Option<&str> {
+        match self.property(name) {
+            Some(PropertyValue::Name(x)) | Some(PropertyValue::Str(x)) => Some(x.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn property_float(&self, name: &str) -> Option<f32> {
+        match self.property(name) {
+            Some(PropertyValue::Float(x)) => Some(*x),
+            _ => None,
+        }
+    }
+
+    fn find_property(&self, key: &str) -> Option<&PropertyValue> {
+        self.properties.get(key).map(|property| &property.value)
+    }
+}
+
+// Map names are not consistently capitalized across game versions.
+fn pretty_map_name(name: &str) -> Option<&'static str> {
+    match name.to_lowercase().as_str() {
+        "arc_standard_p" => Some("Starbase ARC"),
+        "beach_night_p" => Some("Salty Shores (Night)"),
+        "beach_p" => Some("Salty Shores"),
+        "chn_stadium_p" => Some("Forbidden Temple"),
+        "cs_day_p" => Some("Champions Field (Day)"),
+        "cs_p" => Some("Champions Field"),
+        "eurostadium_night_p" => Some("Mannfield (Night)"),
+        "eurostadium_p" => Some("Mannfield"),
+        "eurostadium_rainy_p" => Some("Mannfield (Stormy)"),
+        "eurostadium_snownight_p" => Some("Mannfield (Snowy)"),
+        "farm_p" => Some("Farmstead"),
+        "haunted_trainstation_p" => Some("Urban Central (Haunted)"),
+        "hoopsstadium_p" => Some("Dunk House"),
+        "neotokyo_standard_p" => Some("Neo Tokyo"),
+        "park_night_p" => Some("Beckwith Park (Midnight)"),
+        "park_p" => Some("Beckwith Park"),
+        "park_rainy_p" => Some("Beckwith Park (Stormy)"),
+        "shattershot_p" => Some("Core 707"),
+        "stadium_day_p" => Some("DFH Stadium (Day)"),
+        "stadium_foggy_p" => Some("DFH Stadium (Stormy)"),
+        "stadium_p" => Some("DFH Stadium"),
+        "stadium_race_day_p" => Some("DFH Stadium (Circuit)"),
+        "stadium_winter_p" => Some("DFH Stadium (Snowy)"),
+        "throwbackstadium_p" => Some("Throwback Stadium"),
+        "trainstation_dawn_p" => Some("Urban Central (Dawn)"),
+        "trainstation_night_p" => Some("Urban Central (Night)"),
+        "trainstation_p" => Some("Urban Central"),
+        "underwater_p" => Some("AquaDome"),
+        "utopiastadium_dusk_p" => Some("Utopia Coliseum (Dusk)"),
+        "utopiastadium_p" => Some("Utopia Coliseum"),
+        "utopiastadium_snow_p" => Some("Utopia Coliseum (Snowy)"),
+        "wasteland_night_p" => Some("Wasteland (Night)"),
+        "wasteland_p" => Some("Wasteland"),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Playlist {
+    Duel,
+    Doubles,
+    Standard,
+    Chaos,
+    Rumble,
+    Dropshot,
+    Hoops,
+    Snowday,
+    Private,
+    Unknown(Option<i32>),
+}
+
+impl Playlist {
+    pub fn from_id(id: i32) -> Self {
+        match id {
+            1 | 10 => Playlist::Duel,
+            2 | 11 => Playlist::Doubles,
+            3 | 13 => Playlist::Standard,
+            4 => Playlist::Chaos,
+            6 => Playlist::Private,
+            27 | 37 => Playlist::Hoops,
+            28 | 38 => Playlist::Rumble,
+            29 | 39 => Playlist::Dropshot,
+            30 | 40 => Playlist::Snowday,
+            _ => Playlist::Unknown(Some(id)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Version {
     major: u32,
     minor: u32,
     patch: Option<u32>,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_version(&mut self) -> GetResult<Version> {
         let major = self.get_u32()?;
         let minor = self.get_u32()?;
@@ -141,39 +778,97 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
-struct Text {
+impl Version {
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    pub fn patch(&self) -> Option<u32> {
+        self.patch
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Text {
     size: i32,
     value: String,
 }
 
-impl Get {
+impl Get<'_> {
+    // The size always counts the NUL terminator. Positive sizes are a number
+    // of Windows-1252 bytes and negative sizes are a number of UTF-16 code
+    // units, so both branches read exactly the terminated string.
     fn get_text(&mut self) -> GetResult<Text> {
         let size = self.get_i32()?;
         if size < 0 {
-            let bytes = self.get_vec(i32_usize(-2 * size))?;
+            let len = match size.checked_mul(-2).and_then(i32_usize) {
+                None => Err(GetError::SizeOutOfRange(i64::from(size))),
+                Some(len) => Ok(len),
+            }?;
+            self.check_text_len(size, len)?;
+            let bytes = self.get_vec(len)?;
             match utf_16(&bytes) {
-                None => Err(GetError::InvalidUtf16(bytes)),
-                Some(value) => Ok(Text { size, value }),
+                Err(Utf16Error::InvalidUnits) => Err(GetError::Utf16InvalidUnits(bytes)),
+                Err(Utf16Error::OddLength) => Err(GetError::Utf16OddLength(bytes)),
+                Ok(value) => Ok(Text { size, value }),
             }
         } else {
             let size = if size == 0x0500_0000 { 8 } else { size };
-            let bytes = self.get_vec(i32_usize(size))?;
+            let len = match i32_usize(size) {
+                None => Err(GetError::SizeOutOfRange(i64::from(size))),
+                Some(len) => Ok(len),
+            }?;
+            self.check_text_len(size, len)?;
+            let bytes = self.get_vec(len)?;
             match windows_1252(&bytes) {
                 None => Err(GetError::InvalidWindows1252(bytes)),
                 Some(value) => Ok(Text { size, value }),
             }
         }
     }
+
+    fn check_text_len(&self, size: i32, len: usize) -> GetResult<()> {
+        if len > self.bytes.len().saturating_sub(self.index) {
+            Err(GetError::TextTooLarge { size })
+        } else {
+            Ok(())
+        }
+    }
 }
 
-#[derive(Debug)]
-struct Dictionary<T> {
+#[cfg(feature = "serde")]
+impl Serialize for Text {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl Text {
+    pub fn size(&self) -> i32 {
+        self.size
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.value.trim_end_matches('\0')
+    }
+
+    pub fn trimmed(&self) -> &str {
+        self.value.strip_suffix('\0').unwrap_or(&self.value)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Dictionary<T> {
     value: Vec<(Text, T)>,
     last: Text,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_dictionary<F, T>(&mut self, get_value: F) -> GetResult<Dictionary<T>>
     where
         F: Fn(&mut Self) -> GetResult<T>,
@@ -191,24 +886,60 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
-struct Property {
+impl<T> Dictionary<T> {
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.value.iter().map(|entry| (entry.0.as_str(), &entry.1))
+    }
+
+    pub fn last(&self) -> &str {
+        self.last.as_str()
+    }
+
+    // Keys are matched without their NUL terminators, like iter returns them.
+    pub fn get(&self, key: &str) -> Option<&T> {
+        let key = key.trim_end_matches('\0');
+        self.value
+            .iter()
+            .find(|entry| entry.0.as_str() == key)
+            .map(|entry| &entry.1)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Property {
     label: Text,
     size: u64,
     value: PropertyValue,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_property(&mut self) -> GetResult<Property> {
         let label = self.get_text()?;
         let size = self.get_u64()?;
-        let value = self.get_property_value(label.value.as_str())?;
+        let value = self.get_property_value(label.value.as_str(), size)?;
         Ok(Property { label, size, value })
     }
 }
 
-#[derive(Debug)]
-enum PropertyValue {
+impl Property {
+    pub fn label(&self) -> &str {
+        self.label.as_str()
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn value(&self) -> &PropertyValue {
+        &self.value
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+pub enum PropertyValue {
     Array(List<Dictionary<Property>>),
     Bool(u8),
     Byte { key: Text, value: Option<Text> },
@@ -217,10 +948,11 @@ enum PropertyValue {
     Name(Text),
     QWord(u64),
     Str(Text),
+    Unknown { label: String, bytes: Vec<u8> },
 }
 
-impl Get {
-    fn get_property_value(&mut self, label: &str) -> GetResult<PropertyValue> {
+impl Get<'_> {
+    fn get_property_value(&mut self, label: &str, size: u64) -> GetResult<PropertyValue> {
         match label {
             "ArrayProperty\0" => self.get_property_value_array(),
             "BoolProperty\0" => self.get_property_value_bool(),
@@ -230,10 +962,25 @@ impl Get {
             "NameProperty\0" => self.get_property_value_name(),
             "QWordProperty\0" => self.get_property_value_qword(),
             "StrProperty\0" => self.get_property_value_str(),
+            _ if self.options.lenient => self.get_property_value_unknown(label, size),
             _ => Err(GetError::UnknownProperty(String::from(label))),
         }
     }
 
+    // The size is only trusted for properties that cannot be decoded, so
+    // that lenient parsing can skip over them.
+    fn get_property_value_unknown(&mut self, label: &str, size: u64) -> GetResult<PropertyValue> {
+        let len = match u64_usize(size) {
+            None => Err(GetError::SizeOutOfRange(u64_i64(size))),
+            Some(len) => Ok(len),
+        }?;
+        let bytes = self.get_vec(len)?;
+        Ok(PropertyValue::Unknown {
+            label: String::from(label),
+            bytes,
+        })
+    }
+
     fn get_property_value_array(&mut self) -> GetResult<PropertyValue> {
         let x = self.get_list(|this| this.get_dictionary(|that| that.get_property()))?;
         Ok(PropertyValue::Array(x))
@@ -246,12 +993,13 @@ impl Get {
 
     fn get_property_value_byte(&mut self) -> GetResult<PropertyValue> {
         let key = self.get_text()?;
-        let value = if key.value.as_str() == "OnlinePlatform_Steam\0" {
-            Ok(None)
+        // Platform bytes name the platform in the key and have no value.
+        let value = if key.value.starts_with("OnlinePlatform_") {
+            None
         } else {
             let x = self.get_text()?;
-            Ok(Some(x))
-        }?;
+            Some(x)
+        };
         Ok(PropertyValue::Byte { key, value })
     }
 
@@ -281,19 +1029,23 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
-struct List<T> {
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct List<T> {
     size: u32,
     value: Vec<T>,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_list<F, T>(&mut self, get_value: F) -> GetResult<List<T>>
     where
         F: Fn(&mut Self) -> GetResult<T>,
     {
         let size = self.get_u32()?;
-        let mut value = Vec::with_capacity(u32_usize(size));
+        // The size comes from the input, so do not trust it for allocation.
+        // Every element takes at least a byte.
+        let remaining = self.bytes.len().saturating_sub(self.index);
+        let mut value = Vec::with_capacity(get_size(size)?.min(remaining));
         for _ in 0..size {
             let x = get_value(self)?;
             value.push(x)
@@ -302,8 +1054,19 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
-struct Content {
+impl<T> List<T> {
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn value(&self) -> &[T] {
+        &self.value
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Content {
     levels: List<Text>,
     keyframes: List<Keyframe>,
     size: u32,
@@ -315,26 +1078,48 @@ struct Content {
     classes: List<Class>,
     caches: List<Cache>,
     frames: Vec<Frame>,
+    consumed_bits: usize,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_content(&mut self, header: &Header) -> GetResult<Content> {
-        let levels = self.get_list(Self::get_text)?;
-        let keyframes = self.get_list(Self::get_keyframe)?;
-        let size = self.get_u32()?;
-        let bytes = self.get_vec(u32_usize(size))?;
-        let messages = self.get_list(Self::get_message)?;
+        let (mut content, mut decoder) = self.get_content_without_frames(header)?;
+        let remaining = decoder.remaining();
+        content.frames = decoder.decode(remaining)?;
+        content.consumed_bits = decoder.bit_get.get_bit_index();
+        Ok(content)
+    }
+
+    fn get_content_without_frames(
+        &mut self,
+        header: &Header,
+    ) -> GetResult<(Content, FrameDecoder)> {
+        let (content, bytes) = self.get_content_lists()?;
+        let context = Context::new(
+            header,
+            &content.names,
+            &content.objects,
+            &content.classes,
+            &content.caches,
+            self.options.clone(),
+        );
+        let decoder = FrameDecoder::new(bytes, context)?;
+        Ok((content, decoder))
+    }
+
+    fn get_content_lists(&mut self) -> GetResult<(Content, Vec<u8>)> {
+        let levels = self.get_list(Self::get_text)?;
+        let keyframes = self.get_list(Self::get_keyframe)?;
+        let size = self.get_u32()?;
+        let bytes = self.get_vec(get_size(size)?)?;
+        let messages = self.get_list(Self::get_message)?;
         let marks = self.get_list(Self::get_mark)?;
         let packages = self.get_list(Self::get_text)?;
         let objects = self.get_list(Self::get_text)?;
         let names = self.get_list(Self::get_text)?;
         let classes = self.get_list(Self::get_class)?;
         let caches = self.get_list(Self::get_cache)?;
-        let frames = Self::get_frames(
-            bytes,
-            &mut Context::new(header, &names, &objects, &classes, &caches),
-        )?;
-        Ok(Content {
+        let content = Content {
             levels,
             keyframes,
             size,
@@ -345,19 +1130,491 @@ impl Get {
             names,
             classes,
             caches,
-            frames,
+            frames: Vec::new(),
+            consumed_bits: 0,
+        };
+        Ok((content, bytes))
+    }
+}
+
+impl Content {
+    pub fn levels(&self) -> &[Text] {
+        &self.levels.value
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes.value
+    }
+
+    /// Iterates over the decoded frames in order.
+    ///
+    /// ```no_run
+    /// let bytes = std::fs::read("example.replay").unwrap();
+    /// let replay = aftershock::parse(&bytes).unwrap();
+    /// let replications: usize = replay
+    ///     .content()
+    ///     .frames()
+    ///     .map(|frame| frame.replications().len())
+    ///     .sum();
+    /// println!("{} replications", replications);
+    /// ```
+    pub fn frames(&self) -> impl Iterator<Item = &Frame> {
+        self.frames.iter()
+    }
+
+    pub fn messages(&self) -> &[Message] {
+        &self.messages.value
+    }
+
+    pub fn marks(&self) -> &[Mark] {
+        &self.marks.value
+    }
+
+    pub fn packages(&self) -> &[Text] {
+        &self.packages.value
+    }
+
+    pub fn classes(&self) -> &[Class] {
+        &self.classes.value
+    }
+
+    pub fn network_bit_length(&self) -> u64 {
+        8 * u32_u64(self.size)
+    }
+
+    // Keyframe offsets are bit positions in the network stream. Without a
+    // frame count only the offsets can be checked.
+    pub fn validate_keyframes(&self, num_frames: Option<u32>) -> GetResult<()> {
+        let bit_length = self.network_bit_length();
+        for keyframe in self.keyframes() {
+            let is_past_frames = match num_frames {
+                Some(num_frames) => keyframe.frame >= num_frames,
+                None => false,
+            };
+            if is_past_frames || u32_u64(keyframe.offset) >= bit_length {
+                return Err(GetError::KeyframeOutOfRange {
+                    frame: keyframe.frame,
+                    offset: keyframe.offset,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn network_bits_consumed(&self) -> u64 {
+        usize_u64(self.consumed_bits)
+    }
+
+    // Maps each class id to the names of its attributes by stream id,
+    // including the ones it inherits.
+    pub fn attribute_tables(&self) -> BTreeMap<u32, BTreeMap<u32, String>> {
+        Context::get_attributes(&self.caches)
+            .iter()
+            .map(|(&class_id, attributes)| (class_id, self.name_attributes(attributes)))
+            .collect()
+    }
+
+    fn name_attributes(&self, attributes: &BTreeMap<u32, u32>) -> BTreeMap<u32, String> {
+        let objects = &self.objects.value;
+        attributes
+            .iter()
+            .filter_map(|(&stream_id, &object_id)| {
+                u32_usize(object_id)
+                    .and_then(|i| objects.get(i))
+                    .map(|object| (stream_id, object.value.trim_end_matches('\0').to_string()))
+            })
+            .collect()
+    }
+
+    pub fn keyframe_index(&self) -> KeyframeIndex<'_> {
+        KeyframeIndex {
+            keyframes: &self.keyframes.value,
+        }
+    }
+
+    pub fn frames_with_keyframes(&self) -> impl Iterator<Item = (&Frame, bool)> {
+        let keyframes = self.keyframe_index();
+        self.frames.iter().enumerate().map(move |(index, frame)| {
+            let is_keyframe = usize_u32(index)
+                .and_then(|i| keyframes.at_frame(i))
+                .is_some_and(|keyframe| u32_usize(keyframe.frame) == Some(index));
+            (frame, is_keyframe)
+        })
+    }
+
+    pub fn frame_at_offset(&self, offset: u32) -> Option<u32> {
+        // Keyframe offsets are in bits but this offset is in bytes. An offset
+        // too big to convert is past every keyframe.
+        let bits = offset.saturating_mul(8);
+        self.keyframe_index()
+            .at_offset(bits)
+            .map(|keyframe| keyframe.frame)
+    }
+
+    pub fn state_transitions(&self) -> Vec<StateTransition> {
+        self.attribute_updates()
+            .filter(|&(_, _, _, attribute)| {
+                attribute.object.as_str() == "TAGame.GameEvent_TA:ReplicatedStateName\0"
+            })
+            .filter_map(|(index, frame, _, attribute)| match attribute.value {
+                AttributeValue::Int(x) => {
+                    i32_usize(x)
+                        .and_then(|i| self.names.value.get(i))
+                        .map(|name| StateTransition {
+                            frame: index,
+                            time: frame.time,
+                            name: name.value.trim_end_matches('\0').to_string(),
+                        })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    // GameTime is only the configured match length, so the clock comes from
+    // the seconds remaining, which counts down while the ball is in play.
+    pub fn game_times(&self) -> Vec<GameTime> {
+        self.attribute_updates()
+            .filter_map(|(index, frame, _, attribute)| {
+                match (attribute.object.as_str(), &attribute.value) {
+                    ("TAGame.GameEvent_Soccar_TA:SecondsRemaining\0", &AttributeValue::Int(x)) => {
+                        Some(GameTime {
+                            frame: index,
+                            time: frame.time,
+                            seconds_remaining: x,
+                        })
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    // Events are attributed to the player when a player replication info
+    // replicated them. Those that replicate on the game event, like most stat
+    // events, do not say who earned them.
+    pub fn stat_events(&self) -> Vec<StatEvent> {
+        let mut players = HashSet::new();
+        let mut events = Vec::new();
+        for (index, frame) in self.frames.iter().enumerate() {
+            for replication in &frame.replications {
+                let actor = u32_i32(replication.actor.value);
+                let attributes = match &replication.value {
+                    ReplicationValue::Created { class, .. } => {
+                        if class.as_str() == "TAGame.PRI_TA\0" {
+                            players.insert(actor);
+                        } else {
+                            players.remove(&actor);
+                        }
+                        continue;
+                    }
+                    ReplicationValue::Updated(attributes) => attributes,
+                    ReplicationValue::Destroyed => {
+                        players.remove(&actor);
+                        continue;
+                    }
+                };
+                for attribute in attributes {
+                    if let AttributeValue::StatEvent { object_id, .. } = attribute.value {
+                        if let Some(object) =
+                            i32_usize(object_id).and_then(|i| self.objects.value.get(i))
+                        {
+                            events.push(StatEvent {
+                                frame: index,
+                                time: frame.time,
+                                player: if players.contains(&actor) {
+                                    Some(actor)
+                                } else {
+                                    None
+                                },
+                                event: object.value.trim_end_matches('\0').to_string(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    // Platforms do not replicate which side of the field they are on, so each
+    // damage is tagged with the team that last touched the ball instead.
+    pub fn tile_damage(&self) -> Vec<TileDamage> {
+        let mut team = None;
+        let mut damage = Vec::new();
+        for (index, _, replication, attribute) in self.attribute_updates() {
+            match (attribute.object.as_str(), &attribute.value) {
+                ("TAGame.Ball_Breakout_TA:LastTeamTouch\0", &AttributeValue::Byte(x)) => {
+                    team = Some(x)
+                }
+                (
+                    "TAGame.BreakOutActor_Platform_TA:DamageState\0",
+                    &AttributeValue::DamageState { tile_state, .. },
+                ) => damage.push(TileDamage {
+                    frame: index,
+                    tile: u32_i32(replication.actor.value),
+                    state: tile_state,
+                    team,
+                }),
+                _ => (),
+            }
+        }
+        damage
+    }
+
+    pub fn player_history_keys(&self) -> BTreeMap<i32, u16> {
+        let mut keys = BTreeMap::new();
+        for (_, _, replication, attribute) in self.attribute_updates() {
+            if let AttributeValue::PlayerHistoryKey(x) = attribute.value {
+                keys.insert(u32_i32(replication.actor.value), x);
+            }
+        }
+        keys
+    }
+
+    pub fn active_mutators(&self) -> BTreeMap<String, f32> {
+        let mut mutators = BTreeMap::new();
+        for (_, _, _, attribute) in self.attribute_updates() {
+            if let AttributeValue::Float(x) = attribute.value {
+                if let Some(default) = mutator_default(&attribute.object) {
+                    let name = attribute.object.trim_end_matches('\0').to_string();
+                    if x == default {
+                        mutators.remove(&name);
+                    } else {
+                        mutators.insert(name, x);
+                    }
+                }
+            }
+        }
+        mutators
+    }
+
+    fn attribute_updates(&self) -> impl Iterator<Item = (usize, &Frame, &Replication, &Attribute)> {
+        self.frames.iter().enumerate().flat_map(|(index, frame)| {
+            frame.replications.iter().flat_map(move |replication| {
+                let attributes: &[Attribute] = match &replication.value {
+                    ReplicationValue::Updated(attributes) => attributes,
+                    _ => &[],
+                };
+                attributes
+                    .iter()
+                    .map(move |attribute| (index, frame, replication, attribute))
+            })
         })
     }
 }
 
-#[derive(Debug)]
-struct Keyframe {
+#[cfg(feature = "analysis")]
+impl Content {
+    pub fn boost_amounts(&self) -> Vec<BoostAmount> {
+        let mut vehicles = HashMap::new();
+        let mut players = HashMap::new();
+        let mut amounts = Vec::new();
+        for (index, _, replication, attribute) in self.attribute_updates() {
+            let actor = u32_i32(replication.actor.value);
+            match (attribute.object.as_str(), &attribute.value) {
+                ("TAGame.CarComponent_TA:Vehicle\0", &AttributeValue::FlaggedInt { int, .. }) => {
+                    vehicles.insert(actor, int);
+                }
+                (
+                    "Engine.Pawn:PlayerReplicationInfo\0",
+                    &AttributeValue::FlaggedInt { int, .. },
+                ) => {
+                    players.insert(actor, int);
+                }
+                (
+                    "TAGame.CarComponent_Boost_TA:ReplicatedBoostAmount\0",
+                    &AttributeValue::Byte(x),
+                ) => {
+                    if let Some(&player) = vehicles.get(&actor).and_then(|car| players.get(car)) {
+                        amounts.push(BoostAmount {
+                            frame: index,
+                            player,
+                            boost: 100. * f32::from(x) / 255.,
+                        })
+                    }
+                }
+                _ => (),
+            }
+        }
+        amounts
+    }
+
+    // A touch is a change in the ball's velocity of at least speed_change
+    // while a car is within distance of it. Both are in the units of the
+    // stream, which are hundredths of a unit since net version 7.
+    pub fn touches(&self, distance: i32, speed_change: i32) -> Vec<Touch> {
+        let mut balls = HashMap::new();
+        let mut cars = HashMap::new();
+        let mut touches = Vec::new();
+        for (index, frame) in self.frames.iter().enumerate() {
+            let mut moved = Vec::new();
+            for replication in &frame.replications {
+                let actor = u32_i32(replication.actor.value);
+                match &replication.value {
+                    ReplicationValue::Created { class, .. } => {
+                        balls.remove(&actor);
+                        cars.remove(&actor);
+                        if class.starts_with("TAGame.Ball_") {
+                            balls.insert(actor, None);
+                        } else if class.starts_with("TAGame.Car_") {
+                            cars.insert(actor, None);
+                        }
+                    }
+                    ReplicationValue::Destroyed => {
+                        balls.remove(&actor);
+                        cars.remove(&actor);
+                    }
+                    ReplicationValue::Updated(attributes) => {
+                        for attribute in attributes {
+                            if let AttributeValue::RigidBody {
+                                location,
+                                linear_velocity,
+                                ..
+                            } = &attribute.value
+                            {
+                                if balls.contains_key(&actor) {
+                                    moved.push((actor, location, linear_velocity));
+                                } else if cars.contains_key(&actor) {
+                                    cars.insert(actor, Some(location.clone()));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            // Cars are updated first so that a touch uses where they are in
+            // the same frame as the ball.
+            for (ball, location, velocity) in moved {
+                let previous = balls.insert(ball, velocity.clone()).and_then(|x| x);
+                let changed = match (previous, velocity) {
+                    (Some(before), Some(after)) => {
+                        squared_distance(&before, after) >= squared(speed_change)
+                    }
+                    _ => false,
+                };
+                if !changed {
+                    continue;
+                }
+                let nearest = cars
+                    .iter()
+                    .filter_map(|(&car, car_location)| {
+                        car_location
+                            .as_ref()
+                            .map(|x| (squared_distance(x, location), car))
+                    })
+                    .filter(|&(x, _)| x <= squared(distance))
+                    .min();
+                if let Some((_, car)) = nearest {
+                    touches.push(Touch {
+                        frame: index,
+                        actor: car,
+                        position: location.clone(),
+                    })
+                }
+            }
+        }
+        touches
+    }
+}
+
+#[cfg(feature = "analysis")]
+fn squared(x: i32) -> i64 {
+    i64::from(x) * i64::from(x)
+}
+
+#[cfg(feature = "analysis")]
+fn squared_distance(a: &Vector, b: &Vector) -> i64 {
+    squared(a.x - b.x) + squared(a.y - b.y) + squared(a.z - b.z)
+}
+
+#[cfg(feature = "analysis")]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct BoostAmount {
+    pub frame: usize,
+    pub player: i32,
+    pub boost: f32,
+}
+
+#[cfg(feature = "analysis")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Touch {
+    pub frame: usize,
+    pub actor: i32,
+    pub position: Vector,
+}
+
+fn mutator_default(name: &str) -> Option<f32> {
+    match name {
+        "Engine.WorldInfo:WorldGravityZ\0" => Some(-650.),
+        "Engine.WorldInfo:TimeDilation\0"
+        | "TAGame.Ball_TA:ReplicatedAddedCarBounceScale\0"
+        | "TAGame.Ball_TA:ReplicatedBallGravityScale\0"
+        | "TAGame.Ball_TA:ReplicatedBallMaxLinearSpeedScale\0"
+        | "TAGame.Ball_TA:ReplicatedBallScale\0"
+        | "TAGame.Ball_TA:ReplicatedWorldBounceScale\0"
+        | "TAGame.Car_TA:AddedBallForceMultiplier\0"
+        | "TAGame.Car_TA:AddedCarForceMultiplier\0"
+        | "TAGame.Car_TA:ReplicatedCarScale\0" => Some(1.),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GameTime {
+    pub frame: usize,
+    pub time: f32,
+    pub seconds_remaining: i32,
+}
+
+impl GameTime {
+    // How much further the frame time moved than the clock counted down since
+    // an earlier sample, in seconds. The clock stops between goals and
+    // kickoffs, so only samples from the same stretch of play line up.
+    pub fn drift_since(&self, earlier: &GameTime) -> f32 {
+        let elapsed = i32_f32(earlier.seconds_remaining) - i32_f32(self.seconds_remaining);
+        (self.time - earlier.time) - elapsed
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TileDamage {
+    pub frame: usize,
+    pub tile: i32,
+    pub state: u8,
+    pub team: Option<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct StatEvent {
+    pub frame: usize,
+    pub time: f32,
+    pub player: Option<i32>,
+    pub event: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct StateTransition {
+    pub frame: usize,
+    pub time: f32,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Keyframe {
     time: f32,
     frame: u32,
     offset: u32,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_keyframe(&mut self) -> GetResult<Keyframe> {
         let time = self.get_f32()?;
         let frame = self.get_u32()?;
@@ -370,14 +1627,56 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
-struct Message {
+impl Keyframe {
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct KeyframeIndex<'a> {
+    keyframes: &'a [Keyframe],
+}
+
+impl<'a> KeyframeIndex<'a> {
+    pub fn at_time(&self, time: f32) -> Option<&'a Keyframe> {
+        self.at(|keyframe| keyframe.time <= time)
+    }
+
+    pub fn at_frame(&self, frame: u32) -> Option<&'a Keyframe> {
+        self.at(|keyframe| keyframe.frame <= frame)
+    }
+
+    pub fn at_offset(&self, offset: u32) -> Option<&'a Keyframe> {
+        self.at(|keyframe| keyframe.offset <= offset)
+    }
+
+    fn at<F>(&self, is_before: F) -> Option<&'a Keyframe>
+    where
+        F: Fn(&Keyframe) -> bool,
+    {
+        let index = self.keyframes.partition_point(is_before);
+        index.checked_sub(1).map(|i| &self.keyframes[i])
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Message {
     frame: u32,
     label: Text,
     value: Text,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_message(&mut self) -> GetResult<Message> {
         let frame = self.get_u32()?;
         let label = self.get_text()?;
@@ -390,13 +1689,28 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
-struct Mark {
+impl Message {
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    pub fn label(&self) -> &str {
+        self.label.as_str()
+    }
+
+    pub fn value(&self) -> &str {
+        self.value.as_str()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Mark {
     value: Text,
     frame: u32,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_mark(&mut self) -> GetResult<Mark> {
         let value = self.get_text()?;
         let frame = self.get_u32()?;
@@ -404,13 +1718,24 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
-struct Class {
+impl Mark {
+    pub fn value(&self) -> &str {
+        self.value.as_str()
+    }
+
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Class {
     name: Text,
     id: u32,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_class(&mut self) -> GetResult<Class> {
         let name = self.get_text()?;
         let id = self.get_u32()?;
@@ -418,7 +1743,18 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
+impl Class {
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 struct Cache {
     class: u32,
     parent: u32,
@@ -426,7 +1762,7 @@ struct Cache {
     objects: List<Object>,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_cache(&mut self) -> GetResult<Cache> {
         let class = self.get_u32()?;
         let parent = self.get_u32()?;
@@ -441,13 +1777,14 @@ impl Get {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 struct Object {
     index: u32,
     id: u32,
 }
 
-impl Get {
+impl Get<'_> {
     fn get_object(&mut self) -> GetResult<Object> {
         let index = self.get_u32()?;
         let id = self.get_u32()?;
@@ -457,6 +1794,7 @@ impl Get {
 
 struct Context {
     num_frames: usize,
+    frame: usize,
     max_channels: u32,
     version: (u32, u32, u32),
     names: Vec<String>,
@@ -466,6 +1804,7 @@ struct Context {
     classes_with_rotation: HashSet<&'static str>,
     actors: HashMap<u32, u32>,
     attributes: HashMap<u32, BTreeMap<u32, u32>>,
+    options: Options,
 }
 
 impl Context {
@@ -475,9 +1814,11 @@ impl Context {
         objects: &List<Text>,
         classes: &List<Class>,
         caches: &List<Cache>,
+        options: Options,
     ) -> Self {
         Context {
             num_frames: Self::get_num_frames(header),
+            frame: 0,
             max_channels: Self::get_max_channels(header),
             version: Self::get_version(header),
             names: Self::get_names(names),
@@ -487,6 +1828,7 @@ impl Context {
             classes_with_rotation: Self::get_classes_with_rotation(),
             actors: HashMap::new(),
             attributes: Self::get_attributes(caches),
+            options,
         }
     }
 
@@ -503,7 +1845,7 @@ impl Context {
                     value: PropertyValue::Int(num_frames),
                     ..
                 },
-            )) => u32_usize(num_frames),
+            )) => u32_usize(num_frames).unwrap_or(0),
             _ => 0,
         }
     }
@@ -557,6 +1899,8 @@ impl Context {
     fn get_classes_with_location() -> HashSet<&'static str> {
         [
             "TAGame.Ball_Breakout_TA\0",
+            "TAGame.Ball_God_TA\0",
+            "TAGame.Ball_Haunted_TA\0",
             "TAGame.Ball_TA\0",
             "TAGame.CameraSettingsActor_TA\0",
             "TAGame.Car_Season_TA\0",
@@ -566,11 +1910,15 @@ impl Context {
             "TAGame.CarComponent_DoubleJump_TA\0",
             "TAGame.CarComponent_FlipCar_TA\0",
             "TAGame.CarComponent_Jump_TA\0",
+            "TAGame.GameEvent_Football_TA\0",
+            "TAGame.GameEvent_GodBall_TA\0",
             "TAGame.GameEvent_Season_TA\0",
             "TAGame.GameEvent_Soccar_TA\0",
             "TAGame.GameEvent_SoccarPrivate_TA\0",
             "TAGame.GameEvent_SoccarSplitscreen_TA\0",
             "TAGame.GRI_TA\0",
+            "TAGame.InMapScoreboard_TA\0",
+            "TAGame.MaxTimeWarningData_TA\0",
             "TAGame.PRI_TA\0",
             "TAGame.SpecialPickup_BallCarSpring_TA\0",
             "TAGame.SpecialPickup_BallFreeze_TA\0",
@@ -579,8 +1927,11 @@ impl Context {
             "TAGame.SpecialPickup_BallVelcro_TA\0",
             "TAGame.SpecialPickup_Batarang_TA\0",
             "TAGame.SpecialPickup_BoostOverride_TA\0",
+            "TAGame.SpecialPickup_Football_TA\0",
             "TAGame.SpecialPickup_GrapplingHook_TA\0",
+            "TAGame.SpecialPickup_HauntedBallBeam_TA\0",
             "TAGame.SpecialPickup_HitForce_TA\0",
+            "TAGame.SpecialPickup_Rugby_TA\0",
             "TAGame.SpecialPickup_Swapper_TA\0",
             "TAGame.SpecialPickup_Tornado_TA\0",
             "TAGame.Team_Soccar_TA\0",
@@ -596,6 +1947,8 @@ impl Context {
     fn get_classes_with_rotation() -> HashSet<&'static str> {
         [
             "TAGame.Ball_Breakout_TA\0",
+            "TAGame.Ball_God_TA\0",
+            "TAGame.Ball_Haunted_TA\0",
             "TAGame.Ball_TA\0",
             "TAGame.Car_Season_TA\0",
             "TAGame.Car_TA\0",
@@ -661,33 +2014,132 @@ impl Context {
     }
 }
 
-#[derive(Debug)]
-struct Frame {
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Frame {
     time: f32,
     delta: f32,
     replications: Vec<Replication>,
 }
 
-impl Get {
-    fn get_frames(bytes: Vec<u8>, context: &mut Context) -> GetResult<Vec<Frame>> {
-        match BitGet::new(bytes).get_frames(context) {
-            Err(problem) => Err(GetError::BitGet(problem)),
-            Ok(frames) => Ok(frames),
-        }
+impl Frame {
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn delta(&self) -> f32 {
+        self.delta
+    }
+
+    pub fn replications(&self) -> &[Replication] {
+        &self.replications
     }
 }
 
-impl BitGet {
-    fn get_frames(&mut self, context: &mut Context) -> BitGetResult<Vec<Frame>> {
-        let mut frames = Vec::with_capacity(context.num_frames);
-        for _ in 0..context.num_frames {
-            let frame = self.get_frame(context)?;
-            frames.push(frame)
+pub struct FrameDecoder {
+    bit_get: BitGet,
+    context: Context,
+    decoded: usize,
+    failed: bool,
+}
+
+impl FrameDecoder {
+    fn new(bytes: Vec<u8>, context: Context) -> BitGetResult<Self> {
+        if context.num_frames > context.options.max_frames {
+            return Err(BitGetError::TooManyFrames {
+                num_frames: context.num_frames,
+                max_frames: context.options.max_frames,
+            });
+        }
+        Ok(Self {
+            bit_get: BitGet::new(bytes),
+            context,
+            decoded: 0,
+            failed: false,
+        })
+    }
+
+    pub fn frames_decoded(&self) -> usize {
+        self.decoded
+    }
+
+    pub fn remaining(&self) -> usize {
+        if self.failed {
+            0
+        } else {
+            self.context.num_frames - self.decoded
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    pub fn bits_consumed(&self) -> u64 {
+        usize_u64(self.bit_get.get_bit_index())
+    }
+
+    // Together with Content::attribute_tables, this lets callers explain an
+    // unknown attribute after a failed decode.
+    pub fn actor_class_id(&self, actor: u32) -> Option<u32> {
+        self.context.get_actor_class_id(actor)
+    }
+
+    // Decodes at most count frames and then returns, so callers can yield
+    // between batches. Once a frame fails to decode the stream is desynced and
+    // no more frames are produced.
+    pub fn decode(&mut self, count: usize) -> BitGetResult<Vec<Frame>> {
+        let count = count.min(self.remaining());
+        // The frame count comes from the header, so do not trust it for
+        // allocation. Every frame takes at least 64 bits for its times.
+        let bits = (8 * self.bit_get.bytes.len()).saturating_sub(self.bit_get.get_bit_index());
+        let mut frames = Vec::with_capacity(count.min(bits / 64));
+        for _ in 0..count {
+            self.context.frame = self.decoded;
+            let start = self.bit_get.get_bit_index();
+            match self.bit_get.get_frame(&mut self.context) {
+                Err(problem) => {
+                    self.failed = true;
+                    // Running out of data is only a frame count mismatch when
+                    // there was not even room for the next frame's times.
+                    // Anything else is a truncated frame and keeps its error.
+                    let at_boundary = 8 * self.bit_get.bytes.len() < start + 64;
+                    let ran_out = match problem.root() {
+                        BitGetError::IndexOutOfBounds { .. } => at_boundary,
+                        _ => false,
+                    };
+                    return match problem {
+                        BitGetError::Located { position, .. } if ran_out => {
+                            Err(BitGetError::Located {
+                                position,
+                                problem: Box::new(BitGetError::FrameCountMismatch {
+                                    expected: self.context.num_frames,
+                                    actual: self.decoded,
+                                }),
+                            })
+                        }
+                        _ => Err(problem),
+                    };
+                }
+                Ok(frame) => {
+                    self.decoded += 1;
+                    frames.push(frame)
+                }
+            }
         }
         Ok(frames)
     }
+}
 
+impl BitGet {
     fn get_frame(&mut self, context: &mut Context) -> BitGetResult<Frame> {
+        match self.get_frame_unlocated(context) {
+            Err(problem) => Err(self.locate(problem, context.frame, None)),
+            Ok(frame) => Ok(frame),
+        }
+    }
+
+    fn get_frame_unlocated(&mut self, context: &mut Context) -> BitGetResult<Frame> {
         let time = self.get_f32()?;
         let delta = self.get_f32()?;
         let replications = self.get_replications(context)?;
@@ -697,10 +2149,28 @@ impl BitGet {
             replications,
         })
     }
+
+    // Errors are only located once, as close to where they happened as
+    // possible, so an outer frame does not hide the actor.
+    fn locate(&self, problem: BitGetError, frame: usize, actor: Option<u32>) -> BitGetError {
+        match problem {
+            BitGetError::Located { .. } => problem,
+            _ => BitGetError::Located {
+                position: Position {
+                    byte_index: self.byte_index,
+                    bit_index: self.bit_index,
+                    frame,
+                    actor,
+                },
+                problem: Box::new(problem),
+            },
+        }
+    }
 }
 
-#[derive(Debug)]
-struct Replication {
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Replication {
     actor: U32C,
     value: ReplicationValue,
 }
@@ -722,31 +2192,50 @@ impl BitGet {
 
     fn get_replication(&mut self, context: &mut Context) -> BitGetResult<Replication> {
         let actor = self.get_u32c(context.max_channels)?;
-        let value = self.get_replication_value(context, actor.value)?;
-        if let ReplicationValue::Created { class_id, .. } = value {
-            match context.actors.insert(actor.value, class_id) {
-                _ => (),
+        let value = match self.get_replication_value(context, actor.value) {
+            Err(problem) => return Err(self.locate(problem, context.frame, Some(actor.value))),
+            Ok(value) => value,
+        };
+        match value {
+            ReplicationValue::Created { class_id, .. } => {
+                context.actors.insert(actor.value, class_id);
             }
+            ReplicationValue::Destroyed => {
+                context.actors.remove(&actor.value);
+            }
+            ReplicationValue::Updated(_) => (),
         }
         Ok(Replication { actor, value })
     }
 }
 
-#[derive(Debug)]
-struct U32C {
-    limit: u32,
-    value: u32,
+impl Replication {
+    pub fn actor(&self) -> u32 {
+        self.actor.value
+    }
+
+    pub fn value(&self) -> &ReplicationValue {
+        &self.value
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct U32C {
+    pub limit: u32,
+    pub value: u32,
 }
 
 impl BitGet {
     fn get_u32c(&mut self, limit: u32) -> BitGetResult<U32C> {
         let mut value = 0;
-        let max_index = (limit as f32).log2().ceil() as u32;
+        let max_index = 32 - limit.leading_zeros();
         let mut index = 0;
-        loop {
-            let step = 1 << index;
-            let next_value = value + step;
-            if index >= max_index || next_value > limit {
+        // The index has to be checked before shifting, because limits of 2^31
+        // and above would otherwise shift by 32.
+        while index < max_index {
+            let next_value = value + (1 << index);
+            if next_value > limit {
                 break;
             }
             let flag = self.get_bool()?;
@@ -759,8 +2248,10 @@ impl BitGet {
     }
 }
 
-#[derive(Debug)]
-enum ReplicationValue {
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+pub enum ReplicationValue {
     Created {
         unknown: bool,
         name_index: Option<u32>,
@@ -769,7 +2260,7 @@ enum ReplicationValue {
         object: String, // RO
         class_id: u32,  // RO
         class: String,  // RO
-        location: Option<Location>,
+        location: Option<Vector>,
         rotation: Option<Rotation>,
     },
     Updated(Vec<Attribute>),
@@ -803,13 +2294,13 @@ impl BitGet {
         let name_index = self.get_option(context.version >= (868, 14, 0), Self::get_u32)?;
         let name = match name_index {
             None => Ok(None),
-            Some(index) => match context.names.get(u32_usize(index)) {
+            Some(index) => match u32_usize(index).and_then(|i| context.names.get(i)) {
                 None => Err(BitGetError::UnknownName(index)),
                 Some(name) => Ok(Some(name.clone())),
             },
         }?;
         let object_index = self.get_u32()?;
-        let object = match context.objects.get(u32_usize(object_index)) {
+        let object = match u32_usize(object_index).and_then(|i| context.objects.get(i)) {
             None => Err(BitGetError::UnknownObject(object_index)),
             Some(name) => Ok(name.clone()),
         }?;
@@ -817,7 +2308,9 @@ impl BitGet {
             None => Err(BitGetError::UnknownObjectClass(object.clone())),
             Some((&id, name)) => Ok((id, name.clone())),
         }?;
-        let location = self.get_option(context.has_location(&class), Self::get_location)?;
+        let location = self.get_option(context.has_location(&class), |this| {
+            this.get_location(context)
+        })?;
         let rotation = self.get_option(context.has_rotation(&class), Self::get_rotation)?;
         Ok(ReplicationValue::Created {
             unknown,
@@ -846,30 +2339,65 @@ impl BitGet {
     }
 }
 
-#[derive(Debug)]
-struct Location {
-    size: U32C,
-    x: U32C,
-    y: U32C,
-    z: U32C,
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Vector {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl BitGet {
+    fn get_location(&mut self, context: &Context) -> BitGetResult<Vector> {
+        self.get_vector(context)
+    }
+
+    // Net version 7 raised the largest component size from 19 to 21 bits.
+    fn get_vector(&mut self, context: &Context) -> BitGetResult<Vector> {
+        let limit = if context.version >= (868, 22, 7) {
+            21
+        } else {
+            19
+        };
+        let size = self.get_u32c(limit)?;
+        let bits = size.value + 2;
+        let bias = u32_i32(1 << (size.value + 1));
+        let x = self.get_bits(bits)?;
+        let y = self.get_bits(bits)?;
+        let z = self.get_bits(bits)?;
+        Ok(Vector {
+            x: u32_i32(x) - bias,
+            y: u32_i32(y) - bias,
+            z: u32_i32(z) - bias,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ActiveActor {
+    pub active: bool,
+    pub actor: i32,
 }
 
 impl BitGet {
-    fn get_location(&mut self) -> BitGetResult<Location> {
-        let size = self.get_u32c(19)?;
-        let limit = 4 << size.value;
-        let x = self.get_u32c(limit)?;
-        let y = self.get_u32c(limit)?;
-        let z = self.get_u32c(limit)?;
-        Ok(Location { size, x, y, z })
+    // The actor id is a plain u32 rather than a compressed word.
+    fn get_active_actor(&mut self) -> BitGetResult<ActiveActor> {
+        let active = self.get_bool()?;
+        let actor = self.get_u32()?;
+        Ok(ActiveActor {
+            active,
+            actor: u32_i32(actor),
+        })
     }
 }
 
-#[derive(Debug)]
-struct Rotation {
-    x: Option<i8>,
-    y: Option<i8>,
-    z: Option<i8>,
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Rotation {
+    pub x: Option<i8>,
+    pub y: Option<i8>,
+    pub z: Option<i8>,
 }
 
 impl BitGet {
@@ -884,9 +2412,66 @@ impl BitGet {
     }
 }
 
-#[derive(Debug)]
-struct Attribute {
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Quaternion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl BitGet {
+    fn get_quaternion(&mut self) -> BitGetResult<Quaternion> {
+        let largest = self.get_bits(2)?;
+        let a = self.get_quaternion_component()?;
+        let b = self.get_quaternion_component()?;
+        let c = self.get_quaternion_component()?;
+        let extra = (1. - a * a - b * b - c * c).max(0.).sqrt();
+        let (x, y, z, w) = match largest {
+            0 => (extra, a, b, c),
+            1 => (a, extra, b, c),
+            2 => (a, b, extra, c),
+            _ => (a, b, c, extra),
+        };
+        Ok(Quaternion { x, y, z, w })
+    }
+
+    fn get_quaternion_component(&mut self) -> BitGetResult<f32> {
+        let x = self.get_bits(18)?;
+        let position = i32_f32(u32_i32(x)) / i32_f32((1 << 18) - 1);
+        Ok((position - 0.5) * std::f32::consts::FRAC_1_SQRT_2 * 2.)
+    }
+
+    // Before net version 7 the rotation was sent as pitch, yaw, and roll,
+    // each a fraction of a half turn.
+    fn get_compressed_rotation(&mut self) -> BitGetResult<Quaternion> {
+        let pitch = self.get_compressed_f32()?;
+        let yaw = self.get_compressed_f32()?;
+        let roll = self.get_compressed_f32()?;
+        let half = std::f32::consts::FRAC_PI_2;
+        let (sp, cp) = (pitch * half).sin_cos();
+        let (sy, cy) = (yaw * half).sin_cos();
+        let (sr, cr) = (roll * half).sin_cos();
+        Ok(Quaternion {
+            x: cr * sp * sy - sr * cp * cy,
+            y: -cr * sp * cy - sr * cp * sy,
+            z: cr * cp * sy - sr * sp * cy,
+            w: cr * cp * cy + sr * sp * sy,
+        })
+    }
+
+    fn get_compressed_f32(&mut self) -> BitGetResult<f32> {
+        let x = self.get_u32c(0xffff)?;
+        Ok(i32_f32(u32_i32(x.value) - 0x8000) / i32_f32(0x7fff))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Attribute {
     class_id: u32, // RO
+    class: String, // RO
     stream_id: U32C,
     object_id: u32, // RO
     object: String, // RO
@@ -913,7 +2498,11 @@ impl BitGet {
             None => Err(BitGetError::UnknownActor(actor)),
             Some(id) => Ok(id),
         }?;
-        let attributes = match context.get_class_attributes(class_id) {
+        let class = match context.classes.get(&class_id) {
+            Some(x) => Ok(x.clone()),
+            None => Err(BitGetError::UnknownClass(class_id)),
+        }?;
+        let attributes = match context.get_class_attributes(class_id) {
             Some(x) => Ok(x),
             None => Err(BitGetError::UnknownClass(class_id)),
         }?;
@@ -926,13 +2515,14 @@ impl BitGet {
             Some(&x) => Ok(x),
             None => Err(BitGetError::UnknownStreamId(stream_id.value)),
         }?;
-        let object = match context.objects.get(u32_usize(object_id)) {
+        let object = match u32_usize(object_id).and_then(|i| context.objects.get(i)) {
             Some(x) => Ok(x.clone()),
             None => Err(BitGetError::UnknownAttributeIndex(object_id)),
         }?;
-        let value = self.get_attribute_value(&object)?;
+        let value = self.get_attribute_value(context, &object)?;
         Ok(Attribute {
             class_id,
+            class,
             stream_id,
             object_id,
             object,
@@ -941,641 +2531,1849 @@ impl BitGet {
     }
 }
 
-#[derive(Debug)]
-enum AttributeValue {}
-
-impl BitGet {
-    fn get_attribute_value(&mut self, name: &str) -> BitGetResult<AttributeValue> {
-        // TODO
-        Err(BitGetError::UnknownAttribute(String::from(name)))
+impl Attribute {
+    pub fn class_id(&self) -> u32 {
+        self.class_id
     }
-}
 
-impl Get {
-    fn get_f32(&mut self) -> GetResult<f32> {
-        let x = self.get_u32()?;
-        Ok(u32_f32(x))
+    pub fn class(&self) -> &str {
+        self.class.trim_end_matches('\0')
     }
 
-    fn get_i32(&mut self) -> GetResult<i32> {
-        let x = self.get_u32()?;
-        Ok(u32_i32(x))
+    pub fn stream_id(&self) -> u32 {
+        self.stream_id.value
     }
 
-    fn get_option<F, T>(&mut self, condition: bool, get_value: F) -> GetResult<Option<T>>
-    where
-        F: Fn(&mut Self) -> GetResult<T>,
-    {
-        if condition {
-            let value = get_value(self)?;
-            Ok(Some(value))
-        } else {
-            Ok(None)
-        }
+    pub fn object_id(&self) -> u32 {
+        self.object_id
     }
 
-    fn get_u8(&mut self) -> GetResult<u8> {
-        let x = self.get_vec(1)?;
-        Ok(x[0])
+    pub fn object(&self) -> &str {
+        self.object.trim_end_matches('\0')
     }
 
-    fn get_u16(&mut self) -> GetResult<u16> {
-        let lower = self.get_u8()?;
-        let upper = self.get_u8()?;
-        Ok(u8_u16(lower) | u8_u16(upper) << 8)
+    pub fn value(&self) -> &AttributeValue {
+        &self.value
     }
+}
 
-    fn get_u32(&mut self) -> GetResult<u32> {
-        let lower = self.get_u16()?;
-        let upper = self.get_u16()?;
-        Ok(u16_u32(lower) | u16_u32(upper) << 16)
-    }
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+pub enum AttributeValue {
+    AppliedDamage {
+        id: u8,
+        location: Vector,
+        damage_index: i32,
+        total_damage: i32,
+    },
+    Boolean(bool),
+    Byte(u8),
+    CamSettings {
+        fov: f32,
+        height: f32,
+        angle: f32,
+        distance: f32,
+        stiffness: f32,
+        swivel_speed: f32,
+        transition_speed: Option<f32>,
+    },
+    ClubColors {
+        blue_flag: bool,
+        blue_color: u8,
+        orange_flag: bool,
+        orange_color: u8,
+    },
+    DamageState {
+        tile_state: u8,
+        damaged: bool,
+        offender: i32,
+        ball_location: Vector,
+        direct_hit: bool,
+        immediate: bool,
+    },
+    Demolish {
+        attacker: ActiveActor,
+        victim: ActiveActor,
+        attacker_velocity: Vector,
+        victim_velocity: Vector,
+    },
+    DemolishFx {
+        custom_demo_flag: bool,
+        custom_demo_id: i32,
+        attacker: ActiveActor,
+        victim: ActiveActor,
+        attacker_velocity: Vector,
+        victim_velocity: Vector,
+    },
+    Explosion {
+        actor: ActiveActor,
+        location: Vector,
+    },
+    ExtendedExplosion {
+        actor: ActiveActor,
+        location: Vector,
+        unknown1: bool,
+        unknown2: i32,
+    },
+    FlaggedInt {
+        flag: bool,
+        int: i32,
+    },
+    Float(f32),
+    GameMode(u8),
+    Int(i32),
+    Loadout(Loadout),
+    LoadoutOnline(Vec<Vec<ProductAttribute>>),
+    Loadouts {
+        blue: Loadout,
+        orange: Loadout,
+    },
+    MusicStinger {
+        flag: bool,
+        cue: u32,
+        trigger: u8,
+    },
+    PartyLeader(Option<UniqueId>),
+    Pickup {
+        instigator: Option<u32>,
+        picked_up: bool,
+    },
+    PickupNew {
+        instigator: Option<u32>,
+        picked_up: u8,
+    },
+    PlayerHistoryKey(u16),
+    PrivateMatchSettings {
+        mutators: String,
+        joinable_by: u32,
+        max_players: i32,
+        game_name: String,
+        password: String,
+        flag: bool,
+    },
+    QWord(u64),
+    RepStatTitle {
+        unknown: bool,
+        name: String,
+        flag: bool,
+        object_id: u32,
+        index: u32,
+    },
+    Reservation {
+        number: u32,
+        unique_id: UniqueId,
+        name: Option<String>,
+        unknown1: bool,
+        unknown2: bool,
+        unknown3: Option<u8>,
+    },
+    RigidBody {
+        sleeping: bool,
+        location: Vector,
+        rotation: Quaternion,
+        linear_velocity: Option<Vector>,
+        angular_velocity: Option<Vector>,
+    },
+    Role(NetworkRole),
+    Rotation {
+        pitch: Option<f32>,
+        yaw: Option<f32>,
+        roll: Option<f32>,
+    },
+    StatEvent {
+        unknown: bool,
+        object_id: i32,
+    },
+    String(String),
+    TeamPaint {
+        team: u8,
+        primary_color: u8,
+        accent_color: u8,
+        primary_finish: u32,
+        accent_finish: u32,
+    },
+    UniqueId(UniqueId),
+    WeldedInfo {
+        actor: ActiveActor,
+        offset: Vector,
+        mass: f32,
+        rotation: Rotation,
+    },
+}
 
-    fn get_u64(&mut self) -> GetResult<u64> {
-        let lower = self.get_u32()?;
-        let upper = self.get_u32()?;
-        Ok(u32_u64(lower) | u32_u64(upper) << 32)
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Loadout {
+    pub version: u8,
+    pub body: u32,
+    pub decal: u32,
+    pub wheels: u32,
+    pub rocket_trail: u32,
+    pub antenna: u32,
+    pub topper: u32,
+    pub unknown1: u32,
+    pub unknown2: Option<u32>,
+    pub engine_audio: Option<u32>,
+    pub trail: Option<u32>,
+    pub goal_explosion: Option<u32>,
+    pub banner: Option<u32>,
+    pub unknown3: Option<u32>,
+    pub unknown4: Option<u32>,
+    pub unknown5: Option<u32>,
+    pub unknown6: Option<u32>,
+}
+
+impl BitGet {
+    fn get_loadout(&mut self) -> BitGetResult<Loadout> {
+        let version = self.get_u8()?;
+        let body = self.get_u32()?;
+        let decal = self.get_u32()?;
+        let wheels = self.get_u32()?;
+        let rocket_trail = self.get_u32()?;
+        let antenna = self.get_u32()?;
+        let topper = self.get_u32()?;
+        let unknown1 = self.get_u32()?;
+        let unknown2 = self.get_option(version > 10, Self::get_u32)?;
+        let engine_audio = self.get_option(version >= 16, Self::get_u32)?;
+        let trail = self.get_option(version >= 16, Self::get_u32)?;
+        let goal_explosion = self.get_option(version >= 16, Self::get_u32)?;
+        let banner = self.get_option(version >= 17, Self::get_u32)?;
+        let unknown3 = self.get_option(version >= 19, Self::get_u32)?;
+        let unknown4 = self.get_option(version >= 22, Self::get_u32)?;
+        let unknown5 = self.get_option(version >= 22, Self::get_u32)?;
+        let unknown6 = self.get_option(version >= 22, Self::get_u32)?;
+        Ok(Loadout {
+            version,
+            body,
+            decal,
+            wheels,
+            rocket_trail,
+            antenna,
+            topper,
+            unknown1,
+            unknown2,
+            engine_audio,
+            trail,
+            goal_explosion,
+            banner,
+            unknown3,
+            unknown4,
+            unknown5,
+            unknown6,
+        })
     }
+}
 
-    fn get_vec(&mut self, len: usize) -> GetResult<Vec<u8>> {
-        let bytes = self.peek_vec(len)?;
-        self.index += len;
-        Ok(bytes)
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ProductAttribute {
+    pub unknown: bool,
+    pub object_id: u32,
+    pub object: String,
+    pub value: ProductValue,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+pub enum ProductValue {
+    Painted(u32),
+    SpecialEdition(u32),
+    TeamEdition(u32),
+    Title(String),
+    UserColor(Option<u32>),
+}
+
+impl BitGet {
+    fn get_product_attribute(&mut self, context: &Context) -> BitGetResult<ProductAttribute> {
+        let unknown = self.get_bool()?;
+        let object_id = self.get_u32()?;
+        let object = match u32_usize(object_id).and_then(|i| context.objects.get(i)) {
+            Some(x) => Ok(x.clone()),
+            None => Err(BitGetError::UnknownObject(object_id)),
+        }?;
+        let value = match object.as_str() {
+            "TAGame.ProductAttribute_Painted_TA\0" => {
+                let x = self.get_product_id(context)?;
+                ProductValue::Painted(x)
+            }
+            "TAGame.ProductAttribute_SpecialEdition_TA\0" => {
+                let x = self.get_bits(31)?;
+                ProductValue::SpecialEdition(x)
+            }
+            "TAGame.ProductAttribute_TeamEdition_TA\0" => {
+                let x = self.get_product_id(context)?;
+                ProductValue::TeamEdition(x)
+            }
+            "TAGame.ProductAttribute_TitleID_TA\0" => {
+                let x = self.get_text()?;
+                ProductValue::Title(x.trim_end_matches('\0').to_string())
+            }
+            "TAGame.ProductAttribute_UserColor_TA\0" => {
+                let x = if context.version >= (868, 23, 8) {
+                    Some(self.get_u32()?)
+                } else {
+                    let has_color = self.get_bool()?;
+                    self.get_option(has_color, |this| this.get_bits(31))?
+                };
+                ProductValue::UserColor(x)
+            }
+            _ => return Err(BitGetError::UnknownProduct(object)),
+        };
+        Ok(ProductAttribute {
+            unknown,
+            object_id,
+            object,
+            value,
+        })
     }
 
-    fn peek_vec(&self, len: usize) -> GetResult<Vec<u8>> {
-        let index = self.index + len;
-        match self.bytes.get(self.index..index) {
-            None => Err(GetError::IndexOutOfBounds {
-                index,
-                len: self.bytes.len(),
-            }),
-            Some(bytes) => Ok(bytes.to_vec()),
+    fn get_product_id(&mut self, context: &Context) -> BitGetResult<u32> {
+        if context.version >= (868, 18, 0) {
+            self.get_bits(31)
+        } else {
+            let x = self.get_u32c(13)?;
+            Ok(x.value)
         }
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct UniqueId {
+    pub platform: Platform,
+    pub remote: RemoteId,
+    pub local: u8,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Platform {
+    Splitscreen,
+    Steam,
+    PlayStation,
+    Xbox,
+    QQ,
+    Switch,
+    PsyNet,
+    Epic,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+pub enum RemoteId {
+    Splitscreen(u32),
+    Steam(u64),
+    PlayStation { name: String, code: Vec<u8> },
+    Xbox(u64),
+    QQ(u64),
+    Switch(Vec<u8>),
+    PsyNet(Vec<u8>),
+    PsyNetEpic { psy_net: Vec<u8>, epic: String },
+    Epic(String),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum NetworkRole {
+    None,
+    SimulatedProxy,
+    AutonomousProxy,
+    Authority,
+    Unknown(u32),
+}
+
+// Network roles are enums, by the number of bits they use.
+fn get_enum_width(name: &str) -> Option<u32> {
+    match name {
+        "Engine.Actor:RemoteRole\0" | "Engine.Actor:Role\0" => Some(11),
+        _ => None,
+    }
+}
+
 impl BitGet {
-    fn get_bool(&mut self) -> BitGetResult<bool> {
-        match self.bytes.get(self.byte_index) {
-            None => Err(BitGetError::IndexOutOfBounds {
-                index: self.byte_index,
-                len: self.bytes.len(),
-            }),
-            Some(byte) => {
-                let bit = byte & 1 << self.bit_index != 0;
-                self.bit_index += 1;
-                if self.bit_index == 8 {
-                    self.bit_index = 0;
-                    self.byte_index += 1;
-                }
-                Ok(bit)
+    fn get_attribute_value(
+        &mut self,
+        context: &Context,
+        name: &str,
+    ) -> BitGetResult<AttributeValue> {
+        if let Some(decoder) = context.options.decoders.get(name) {
+            return decoder(&mut BitReader { bit_get: self });
+        }
+        match name {
+            // actors
+            "Engine.Actor:Rotation\0" => self.get_attribute_value_rotation(),
+            "Engine.Actor:bBlockActors\0"
+            | "Engine.Actor:bCollideActors\0"
+            | "Engine.Actor:bHidden\0" => self.get_attribute_value_boolean(),
+            "Engine.Actor:RemoteRole\0" | "Engine.Actor:Role\0" => {
+                self.get_attribute_value_role(name)
+            }
+            "Engine.Pawn:PlayerReplicationInfo\0" | "TAGame.CarComponent_TA:Vehicle\0" => {
+                self.get_attribute_value_flagged_int()
+            }
+            "TAGame.RBActor_TA:ReplicatedRBState\0" => self.get_attribute_value_rigid_body(context),
+            "TAGame.RBActor_TA:WeldedInfo\0" => self.get_attribute_value_welded_info(context),
+            // players
+            "TAGame.PRI_TA:PartyLeader\0" => self.get_attribute_value_party_leader(context),
+            "Engine.PlayerReplicationInfo:Team\0" | "TAGame.PRI_TA:ReplicatedGameEvent\0" => {
+                self.get_attribute_value_flagged_int()
+            }
+            "Engine.PlayerReplicationInfo:PlayerName\0" | "TAGame.Team_TA:CustomTeamName\0" => {
+                self.get_attribute_value_string()
+            }
+            "ProjectX.GRI_X:Reservations\0" => self.get_attribute_value_reservation(context),
+            "Engine.PlayerReplicationInfo:UniqueId\0" => {
+                self.get_attribute_value_unique_id(context)
             }
+            "TAGame.PRI_TA:ClientLoadout\0" => self.get_attribute_value_loadout(),
+            "TAGame.PRI_TA:ClientLoadoutOnline\0" => {
+                self.get_attribute_value_loadout_online(context)
+            }
+            "TAGame.PRI_TA:ClientLoadouts\0" => self.get_attribute_value_loadouts(),
+            // boost
+            "TAGame.VehiclePickup_TA:NewReplicatedPickupData\0" => {
+                self.get_attribute_value_pickup_new()
+            }
+            "TAGame.VehiclePickup_TA:ReplicatedPickupData\0" => self.get_attribute_value_pickup(),
+            "TAGame.CarComponent_Boost_TA:ReplicatedBoostAmount\0" => {
+                self.get_attribute_value_byte()
+            }
+            "TAGame.CarComponent_Boost_TA:RechargeDelay\0"
+            | "TAGame.CarComponent_Boost_TA:RechargeRate\0" => {
+                self.get_attribute_value_float(context)
+            }
+            // bots
+            "Engine.PlayerReplicationInfo:bBot\0" => self.get_attribute_value_boolean(),
+            "TAGame.PRI_TA:BotBannerProductID\0" | "TAGame.PRI_TA:BotProductName\0" => {
+                self.get_attribute_value_int()
+            }
+            // ambient
+            "TAGame.CrowdActor_TA:GameEvent\0"
+            | "TAGame.CrowdActor_TA:ReplicatedOneShotSound\0"
+            | "TAGame.CrowdManager_TA:GameEvent\0"
+            | "TAGame.CrowdManager_TA:ReplicatedGlobalOneShotSound\0" => {
+                self.get_attribute_value_flagged_int()
+            }
+            "TAGame.CrowdActor_TA:ModifiedNoise\0" => self.get_attribute_value_float(context),
+            "TAGame.CrowdActor_TA:ReplicatedCountDownNumber\0" => self.get_attribute_value_int(),
+            // camera
+            "TAGame.CameraSettingsActor_TA:bMouseCameraToggleEnabled\0"
+            | "TAGame.CameraSettingsActor_TA:bUsingBehindView\0"
+            | "TAGame.CameraSettingsActor_TA:bUsingSecondaryCamera\0"
+            | "TAGame.CameraSettingsActor_TA:bUsingSwivel\0"
+            | "TAGame.PRI_TA:bUsingBehindView\0"
+            | "TAGame.PRI_TA:bUsingFreecam\0"
+            | "TAGame.PRI_TA:bUsingSecondaryCamera\0" => self.get_attribute_value_boolean(),
+            "TAGame.CameraSettingsActor_TA:CameraPitch\0"
+            | "TAGame.CameraSettingsActor_TA:CameraYaw\0"
+            | "TAGame.PRI_TA:CameraPitch\0"
+            | "TAGame.PRI_TA:CameraYaw\0" => self.get_attribute_value_byte(),
+            "TAGame.CameraSettingsActor_TA:PRI\0" => self.get_attribute_value_flagged_int(),
+            "TAGame.CameraSettingsActor_TA:ProfileSettings\0"
+            | "TAGame.PRI_TA:CameraSettings\0" => self.get_attribute_value_cam_settings(context),
+            // dropshot
+            "TAGame.Ball_Breakout_TA:AppliedDamage\0" => {
+                self.get_attribute_value_applied_damage(context)
+            }
+            "TAGame.Ball_Breakout_TA:DamageIndex\0" => self.get_attribute_value_int(),
+            "TAGame.Ball_Breakout_TA:LastTeamTouch\0" => self.get_attribute_value_byte(),
+            "TAGame.BreakOutActor_Platform_TA:DamageState\0" => {
+                self.get_attribute_value_damage_state(context)
+            }
+            // match
+            "ProjectX.GRI_X:GameServerID\0" => self.get_attribute_value_qword(),
+            "TAGame.Ball_TA:GameEvent\0" | "TAGame.Team_TA:GameEvent\0" => {
+                self.get_attribute_value_flagged_int()
+            }
+            "TAGame.GameEvent_TA:GameMode\0" => self.get_attribute_value_game_mode(context),
+            "TAGame.GameEvent_Soccar_TA:ReplicatedMusicStinger\0" => {
+                self.get_attribute_value_music_stinger()
+            }
+            "TAGame.Ball_TA:ReplicatedExplosionData\0" => {
+                self.get_attribute_value_explosion(context)
+            }
+            "TAGame.Ball_TA:ReplicatedExplosionDataExtended\0" => {
+                self.get_attribute_value_extended_explosion(context)
+            }
+            "Engine.GameReplicationInfo:ServerName\0"
+            | "ProjectX.GRI_X:MatchGUID\0"
+            | "TAGame.GRI_TA:NewDedicatedServerIP\0" => self.get_attribute_value_string(),
+            "TAGame.GameEvent_Soccar_TA:bMatchEnded\0"
+            | "TAGame.GameEvent_TA:bCanVoteToForfeit\0"
+            | "TAGame.GameEvent_Team_TA:bForfeit\0" => self.get_attribute_value_boolean(),
+            "TAGame.GameEvent_TA:ReplicatedStateIndex\0" => self.get_attribute_value_byte(),
+            // There is no WorldTime here on purpose. No known replay replicates
+            // it, so it is an unknown attribute unless a decoder is registered.
+            "TAGame.GameEvent_Soccar_TA:GameTime\0"
+            | "TAGame.GameEvent_Soccar_TA:RoundNum\0"
+            | "TAGame.GameEvent_Soccar_TA:SecondsRemaining\0"
+            | "TAGame.GameEvent_TA:ReplicatedGameStateTimeRemaining\0"
+            | "TAGame.GameEvent_TA:ReplicatedRoundCountDownNumber\0"
+            | "TAGame.GameEvent_TA:ReplicatedStateName\0" => self.get_attribute_value_int(),
+            "TAGame.GameEvent_Soccar_TA:GameWinner\0"
+            | "TAGame.GameEvent_Soccar_TA:MatchWinner\0" => self.get_attribute_value_flagged_int(),
+            // mutators
+            "TAGame.GameEvent_SoccarPrivate_TA:MatchSettings\0" => {
+                self.get_attribute_value_private_match_settings()
+            }
+            "Engine.WorldInfo:TimeDilation\0"
+            | "Engine.WorldInfo:WorldGravityZ\0"
+            | "TAGame.Ball_TA:ReplicatedAddedCarBounceScale\0"
+            | "TAGame.Ball_TA:ReplicatedBallGravityScale\0"
+            | "TAGame.Ball_TA:ReplicatedBallMaxLinearSpeedScale\0"
+            | "TAGame.Ball_TA:ReplicatedBallScale\0"
+            | "TAGame.Ball_TA:ReplicatedWorldBounceScale\0"
+            | "TAGame.Car_TA:AddedBallForceMultiplier\0"
+            | "TAGame.Car_TA:AddedCarForceMultiplier\0"
+            | "TAGame.Car_TA:ReplicatedCarScale\0" => self.get_attribute_value_float(context),
+            // rumble
+            "TAGame.SpecialPickup_BallVelcro_TA:bBroken\0"
+            | "TAGame.SpecialPickup_BallVelcro_TA:bHit\0"
+            | "TAGame.SpecialPickup_Rugby_TA:bBallWelded\0" => self.get_attribute_value_boolean(),
+            "TAGame.CarComponent_TA:ReplicatedActive\0" => self.get_attribute_value_byte(),
+            "TAGame.Car_TA:RumblePickups\0"
+            | "TAGame.RumblePickups_TA:AttachedPickup\0"
+            | "TAGame.SpecialPickup_Football_TA:WeldedBall\0"
+            | "TAGame.SpecialPickup_Targeted_TA:Targeted\0" => {
+                self.get_attribute_value_flagged_int()
+            }
+            "TAGame.CarComponent_Boost_TA:BoostModifier\0"
+            | "TAGame.CarComponent_TA:ReplicatedActivityTime\0"
+            | "TAGame.SpecialPickup_BallFreeze_TA:RepOrigSpeed\0"
+            | "TAGame.SpecialPickup_BallVelcro_TA:AttachTime\0"
+            | "TAGame.SpecialPickup_BallVelcro_TA:BreakTime\0" => {
+                self.get_attribute_value_float(context)
+            }
+            "TAGame.RumblePickups_TA:ConcurrentItemCount\0" => self.get_attribute_value_int(),
+            // vehicles
+            "TAGame.Car_TA:ClubColors\0" | "TAGame.Team_TA:ClubColors\0" => {
+                self.get_attribute_value_club_colors()
+            }
+            "TAGame.Car_TA:TeamPaint\0" => self.get_attribute_value_team_paint(),
+            "TAGame.Car_TA:ReplicatedDemolish\0" => self.get_attribute_value_demolish(context),
+            "TAGame.Car_TA:ReplicatedDemolish_CustomFX\0" => {
+                self.get_attribute_value_demolish_fx(context)
+            }
+            "TAGame.Vehicle_TA:ReplicatedSteer\0" | "TAGame.Vehicle_TA:ReplicatedThrottle\0" => {
+                self.get_attribute_value_byte()
+            }
+            // stats
+            "TAGame.PRI_TA:RepStatTitles\0" => self.get_attribute_value_rep_stat_title(),
+            "TAGame.PRI_TA:PlayerHistoryValid\0" => self.get_attribute_value_boolean(),
+            "TAGame.PRI_TA:PlayerHistoryKey\0" => self.get_attribute_value_player_history_key(),
+            "TAGame.GameEvent_Soccar_TA:ReplicatedStatEvent\0" => {
+                self.get_attribute_value_stat_event()
+            }
+            _ => Err(BitGetError::UnknownAttribute(String::from(name))),
         }
     }
 
-    fn get_f32(&mut self) -> BitGetResult<f32> {
-        let x = self.get_u32()?;
-        Ok(u32_f32(x))
+    fn get_attribute_value_applied_damage(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let id = self.get_u8()?;
+        let location = self.get_vector(context)?;
+        let damage_index = self.get_u32()?;
+        let total_damage = self.get_u32()?;
+        Ok(AttributeValue::AppliedDamage {
+            id,
+            location,
+            damage_index: u32_i32(damage_index),
+            total_damage: u32_i32(total_damage),
+        })
     }
 
-    fn get_i8(&mut self) -> BitGetResult<i8> {
+    fn get_attribute_value_boolean(&mut self) -> BitGetResult<AttributeValue> {
+        let x = self.get_bool()?;
+        Ok(AttributeValue::Boolean(x))
+    }
+
+    fn get_attribute_value_byte(&mut self) -> BitGetResult<AttributeValue> {
         let x = self.get_u8()?;
-        Ok(u8_i8(x))
+        Ok(AttributeValue::Byte(x))
     }
 
-    fn get_option<F, T>(&mut self, condition: bool, get_value: F) -> BitGetResult<Option<T>>
-    where
-        F: Fn(&mut Self) -> BitGetResult<T>,
-    {
-        if condition {
-            let value = get_value(self)?;
-            Ok(Some(value))
-        } else {
-            Ok(None)
-        }
+    fn get_attribute_value_cam_settings(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let fov = self.get_f32()?;
+        let height = self.get_f32()?;
+        let angle = self.get_f32()?;
+        let distance = self.get_f32()?;
+        let stiffness = self.get_f32()?;
+        let swivel_speed = self.get_f32()?;
+        let transition_speed = self.get_option(context.version >= (868, 20, 0), Self::get_f32)?;
+        Ok(AttributeValue::CamSettings {
+            fov,
+            height,
+            angle,
+            distance,
+            stiffness,
+            swivel_speed,
+            transition_speed,
+        })
     }
 
-    fn get_u8(&mut self) -> BitGetResult<u8> {
-        let a = self.get_bool()?;
-        let b = self.get_bool()?;
-        let c = self.get_bool()?;
-        let d = self.get_bool()?;
-        let e = self.get_bool()?;
-        let f = self.get_bool()?;
-        let g = self.get_bool()?;
-        let h = self.get_bool()?;
-        #[cfg_attr(rustfmt, rustfmt_skip)]
-        Ok(
-            if a { 0b0000_0001 } else { 0 } |
-            if b { 0b0000_0010 } else { 0 } |
-            if c { 0b0000_0100 } else { 0 } |
-            if d { 0b0000_1000 } else { 0 } |
-            if e { 0b0001_0000 } else { 0 } |
-            if f { 0b0010_0000 } else { 0 } |
-            if g { 0b0100_0000 } else { 0 } |
-            if h { 0b1000_0000 } else { 0 },
-        )
+    fn get_attribute_value_club_colors(&mut self) -> BitGetResult<AttributeValue> {
+        let blue_flag = self.get_bool()?;
+        let blue_color = self.get_u8()?;
+        let orange_flag = self.get_bool()?;
+        let orange_color = self.get_u8()?;
+        Ok(AttributeValue::ClubColors {
+            blue_flag,
+            blue_color,
+            orange_flag,
+            orange_color,
+        })
     }
 
-    fn get_u16(&mut self) -> BitGetResult<u16> {
-        let lower = self.get_u8()?;
-        let upper = self.get_u8()?;
-        Ok(u8_u16(lower) | u8_u16(upper) << 8)
+    fn get_attribute_value_damage_state(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let tile_state = self.get_u8()?;
+        let damaged = self.get_bool()?;
+        let offender = self.get_u32()?;
+        let ball_location = self.get_vector(context)?;
+        let direct_hit = self.get_bool()?;
+        let immediate = self.get_bool()?;
+        Ok(AttributeValue::DamageState {
+            tile_state,
+            damaged,
+            offender: u32_i32(offender),
+            ball_location,
+            direct_hit,
+            immediate,
+        })
     }
 
-    fn get_u32(&mut self) -> BitGetResult<u32> {
-        let lower = self.get_u16()?;
-        let upper = self.get_u16()?;
-        Ok(u16_u32(lower) | u16_u32(upper) << 16)
+    // Unlike actor ids in replications, the ids here are plain little-endian
+    // u32s rather than compressed words capped by the channel count.
+    fn get_attribute_value_demolish(&mut self, context: &Context) -> BitGetResult<AttributeValue> {
+        let attacker = self.get_active_actor()?;
+        let victim = self.get_active_actor()?;
+        let attacker_velocity = self.get_vector(context)?;
+        let victim_velocity = self.get_vector(context)?;
+        Ok(AttributeValue::Demolish {
+            attacker,
+            victim,
+            attacker_velocity,
+            victim_velocity,
+        })
     }
-}
 
-fn i32_usize(x: i32) -> usize {
-    x as usize
-}
+    fn get_attribute_value_demolish_fx(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let custom_demo_flag = self.get_bool()?;
+        let custom_demo_id = u32_i32(self.get_u32()?);
+        let attacker = self.get_active_actor()?;
+        let victim = self.get_active_actor()?;
+        let attacker_velocity = self.get_vector(context)?;
+        let victim_velocity = self.get_vector(context)?;
+        Ok(AttributeValue::DemolishFx {
+            custom_demo_flag,
+            custom_demo_id,
+            attacker,
+            victim,
+            attacker_velocity,
+            victim_velocity,
+        })
+    }
 
-fn u8_i8(x: u8) -> i8 {
-    x as i8
-}
+    fn get_attribute_value_explosion(&mut self, context: &Context) -> BitGetResult<AttributeValue> {
+        let actor = self.get_active_actor()?;
+        let location = self.get_vector(context)?;
+        Ok(AttributeValue::Explosion { actor, location })
+    }
 
-fn u8_u16(x: u8) -> u16 {
-    u16::from(x)
-}
+    fn get_attribute_value_extended_explosion(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let actor = self.get_active_actor()?;
+        let location = self.get_vector(context)?;
+        let unknown1 = self.get_bool()?;
+        let unknown2 = self.get_u32()?;
+        Ok(AttributeValue::ExtendedExplosion {
+            actor,
+            location,
+            unknown1,
+            unknown2: u32_i32(unknown2),
+        })
+    }
 
-fn u8_usize(x: u8) -> usize {
-    x as usize
-}
+    fn get_attribute_value_flagged_int(&mut self) -> BitGetResult<AttributeValue> {
+        let flag = self.get_bool()?;
+        let int = self.get_u32()?;
+        Ok(AttributeValue::FlaggedInt {
+            flag,
+            int: u32_i32(int),
+        })
+    }
 
-fn u16_u32(x: u16) -> u32 {
-    u32::from(x)
-}
+    fn get_attribute_value_float(&mut self, context: &Context) -> BitGetResult<AttributeValue> {
+        let bits = self.get_u32()?;
+        let x = u32_f32(bits);
+        match (x.classify(), context.options.float_policy) {
+            (FpCategory::Normal, _) | (FpCategory::Zero, _) | (_, FloatPolicy::Allow) => {
+                Ok(AttributeValue::Float(x))
+            }
+            (_, FloatPolicy::Reject) => Err(BitGetError::InvalidFloat(bits)),
+            (_, FloatPolicy::Zero) => Ok(AttributeValue::Float(0.)),
+        }
+    }
 
-fn u32_f32(x: u32) -> f32 {
-    f32::from_bits(x)
-}
+    fn get_attribute_value_game_mode(&mut self, context: &Context) -> BitGetResult<AttributeValue> {
+        let bits = if context.version >= (868, 12, 0) {
+            8
+        } else {
+            2
+        };
+        let x = self.get_bits(bits)?;
+        Ok(AttributeValue::GameMode(u32_u8(x).unwrap_or(0)))
+    }
 
-fn u32_i32(x: u32) -> i32 {
-    x as i32
-}
+    fn get_attribute_value_int(&mut self) -> BitGetResult<AttributeValue> {
+        let x = self.get_u32()?;
+        Ok(AttributeValue::Int(u32_i32(x)))
+    }
 
-fn u32_u8(x: u32) -> u8 {
-    x as u8
-}
+    fn get_attribute_value_loadout(&mut self) -> BitGetResult<AttributeValue> {
+        let x = self.get_loadout()?;
+        Ok(AttributeValue::Loadout(x))
+    }
 
-fn u32_u64(x: u32) -> u64 {
-    u64::from(x)
-}
+    fn get_attribute_value_loadout_online(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let size = self.get_u8()?;
+        let mut loadouts = Vec::with_capacity(u8_usize(size));
+        for _ in 0..size {
+            let count = self.get_u8()?;
+            let mut attributes = Vec::with_capacity(u8_usize(count));
+            for _ in 0..count {
+                let attribute = self.get_product_attribute(context)?;
+                attributes.push(attribute)
+            }
+            loadouts.push(attributes)
+        }
+        Ok(AttributeValue::LoadoutOnline(loadouts))
+    }
 
-fn u32_usize(x: u32) -> usize {
-    x as usize
-}
+    fn get_attribute_value_loadouts(&mut self) -> BitGetResult<AttributeValue> {
+        let blue = self.get_loadout()?;
+        let orange = self.get_loadout()?;
+        Ok(AttributeValue::Loadouts { blue, orange })
+    }
 
-fn crc_32(bytes: &[u8]) -> u32 {
-    !bytes.iter().fold(0x1034_0dfe, |crc, byte| {
-        crc << 8 ^ CRC_32[u8_usize(byte ^ u32_u8(crc >> 24))]
-    })
-}
+    fn get_attribute_value_music_stinger(&mut self) -> BitGetResult<AttributeValue> {
+        let flag = self.get_bool()?;
+        let cue = self.get_u32()?;
+        let trigger = self.get_u8()?;
+        Ok(AttributeValue::MusicStinger { flag, cue, trigger })
+    }
 
-const CRC_32: [u32; 256] = [
-    0x0000_0000,
-    0x04c1_1db7,
-    0x0982_3b6e,
-    0x0d43_26d9,
-    0x1304_76dc,
-    0x17c5_6b6b,
-    0x1a86_4db2,
-    0x1e47_5005,
-    0x2608_edb8,
-    0x22c9_f00f,
-    0x2f8a_d6d6,
-    0x2b4b_cb61,
-    0x350c_9b64,
-    0x31cd_86d3,
-    0x3c8e_a00a,
-    0x384f_bdbd,
-    0x4c11_db70,
-    0x48d0_c6c7,
-    0x4593_e01e,
-    0x4152_fda9,
-    0x5f15_adac,
-    0x5bd4_b01b,
-    0x5697_96c2,
-    0x5256_8b75,
-    0x6a19_36c8,
-    0x6ed8_2b7f,
-    0x639b_0da6,
-    0x675a_1011,
-    0x791d_4014,
-    0x7ddc_5da3,
-    0x709f_7b7a,
-    0x745e_66cd,
-    0x9823_b6e0,
-    0x9ce2_ab57,
-    0x91a1_8d8e,
-    0x9560_9039,
-    0x8b27_c03c,
-    0x8fe6_dd8b,
-    0x82a5_fb52,
-    0x8664_e6e5,
-    0xbe2b_5b58,
-    0xbaea_46ef,
-    0xb7a9_6036,
-    0xb368_7d81,
-    0xad2f_2d84,
-    0xa9ee_3033,
-    0xa4ad_16ea,
-    0xa06c_0b5d,
-    0xd432_6d90,
-    0xd0f3_7027,
-    0xddb0_56fe,
-    0xd971_4b49,
-    0xc736_1b4c,
-    0xc3f7_06fb,
-    0xceb4_2022,
-    0xca75_3d95,
-    0xf23a_8028,
-    0xf6fb_9d9f,
-    0xfbb8_bb46,
-    0xff79_a6f1,
-    0xe13e_f6f4,
-    0xe5ff_eb43,
-    0xe8bc_cd9a,
-    0xec7d_d02d,
-    0x3486_7077,
-    0x3047_6dc0,
-    0x3d04_4b19,
-    0x39c5_56ae,
-    0x2782_06ab,
-    0x2343_1b1c,
-    0x2e00_3dc5,
-    0x2ac1_2072,
-    0x128e_9dcf,
-    0x164f_8078,
-    0x1b0c_a6a1,
-    0x1fcd_bb16,
-    0x018a_eb13,
-    0x054b_f6a4,
-    0x0808_d07d,
-    0x0cc9_cdca,
-    0x7897_ab07,
-    0x7c56_b6b0,
-    0x7115_9069,
-    0x75d4_8dde,
-    0x6b93_dddb,
-    0x6f52_c06c,
-    0x6211_e6b5,
-    0x66d0_fb02,
-    0x5e9f_46bf,
-    0x5a5e_5b08,
-    0x571d_7dd1,
-    0x53dc_6066,
-    0x4d9b_3063,
-    0x495a_2dd4,
-    0x4419_0b0d,
-    0x40d8_16ba,
-    0xaca5_c697,
-    0xa864_db20,
-    0xa527_fdf9,
-    0xa1e6_e04e,
-    0xbfa1_b04b,
-    0xbb60_adfc,
-    0xb623_8b25,
-    0xb2e2_9692,
-    0x8aad_2b2f,
-    0x8e6c_3698,
-    0x832f_1041,
-    0x87ee_0df6,
-    0x99a9_5df3,
-    0x9d68_4044,
-    0x902b_669d,
-    0x94ea_7b2a,
-    0xe0b4_1de7,
-    0xe475_0050,
-    0xe936_2689,
-    0xedf7_3b3e,
-    0xf3b0_6b3b,
-    0xf771_768c,
-    0xfa32_5055,
-    0xfef3_4de2,
-    0xc6bc_f05f,
-    0xc27d_ede8,
-    0xcf3e_cb31,
-    0xcbff_d686,
-    0xd5b8_8683,
-    0xd179_9b34,
-    0xdc3a_bded,
-    0xd8fb_a05a,
-    0x690c_e0ee,
-    0x6dcd_fd59,
-    0x608e_db80,
-    0x644f_c637,
-    0x7a08_9632,
-    0x7ec9_8b85,
-    0x738a_ad5c,
-    0x774b_b0eb,
-    0x4f04_0d56,
-    0x4bc5_10e1,
-    0x4686_3638,
-    0x4247_2b8f,
-    0x5c00_7b8a,
-    0x58c1_663d,
-    0x5582_40e4,
-    0x5143_5d53,
-    0x251d_3b9e,
-    0x21dc_2629,
-    0x2c9f_00f0,
-    0x285e_1d47,
-    0x3619_4d42,
-    0x32d8_50f5,
-    0x3f9b_762c,
-    0x3b5a_6b9b,
-    0x0315_d626,
-    0x07d4_cb91,
-    0x0a97_ed48,
-    0x0e56_f0ff,
-    0x1011_a0fa,
-    0x14d0_bd4d,
-    0x1993_9b94,
-    0x1d52_8623,
-    0xf12f_560e,
-    0xf5ee_4bb9,
-    0xf8ad_6d60,
-    0xfc6c_70d7,
-    0xe22b_20d2,
-    0xe6ea_3d65,
-    0xeba9_1bbc,
-    0xef68_060b,
-    0xd727_bbb6,
-    0xd3e6_a601,
-    0xdea5_80d8,
-    0xda64_9d6f,
-    0xc423_cd6a,
-    0xc0e2_d0dd,
-    0xcda1_f604,
-    0xc960_ebb3,
-    0xbd3e_8d7e,
-    0xb9ff_90c9,
-    0xb4bc_b610,
-    0xb07d_aba7,
-    0xae3a_fba2,
-    0xaafb_e615,
-    0xa7b8_c0cc,
-    0xa379_dd7b,
-    0x9b36_60c6,
-    0x9ff7_7d71,
-    0x92b4_5ba8,
-    0x9675_461f,
-    0x8832_161a,
-    0x8cf3_0bad,
-    0x81b0_2d74,
-    0x8571_30c3,
-    0x5d8a_9099,
-    0x594b_8d2e,
-    0x5408_abf7,
-    0x50c9_b640,
-    0x4e8e_e645,
-    0x4a4f_fbf2,
-    0x470c_dd2b,
-    0x43cd_c09c,
-    0x7b82_7d21,
-    0x7f43_6096,
-    0x7200_464f,
-    0x76c1_5bf8,
-    0x6886_0bfd,
-    0x6c47_164a,
-    0x6104_3093,
-    0x65c5_2d24,
-    0x119b_4be9,
-    0x155a_565e,
-    0x1819_7087,
-    0x1cd8_6d30,
-    0x029f_3d35,
-    0x065e_2082,
-    0x0b1d_065b,
-    0x0fdc_1bec,
-    0x3793_a651,
-    0x3352_bbe6,
-    0x3e11_9d3f,
-    0x3ad0_8088,
-    0x2497_d08d,
-    0x2056_cd3a,
-    0x2d15_ebe3,
-    0x29d4_f654,
-    0xc5a9_2679,
-    0xc168_3bce,
-    0xcc2b_1d17,
-    0xc8ea_00a0,
-    0xd6ad_50a5,
-    0xd26c_4d12,
-    0xdf2f_6bcb,
-    0xdbee_767c,
-    0xe3a1_cbc1,
-    0xe760_d676,
-    0xea23_f0af,
-    0xeee2_ed18,
-    0xf0a5_bd1d,
-    0xf464_a0aa,
-    0xf927_8673,
-    0xfde6_9bc4,
-    0x89b8_fd09,
-    0x8d79_e0be,
-    0x803a_c667,
-    0x84fb_dbd0,
-    0x9abc_8bd5,
-    0x9e7d_9662,
-    0x933e_b0bb,
-    0x97ff_ad0c,
-    0xafb0_10b1,
-    0xab71_0d06,
-    0xa632_2bdf,
-    0xa2f3_3668,
-    0xbcb4_666d,
-    0xb875_7bda,
-    0xb536_5d03,
-    0xb1f7_40b4,
-];
+    // A party leader with no platform has no remote or local id at all,
+    // unlike a splitscreen unique id which shares platform 0.
+    fn get_attribute_value_party_leader(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let platform = self.get_u8()?;
+        let x = if platform == 0 {
+            None
+        } else {
+            Some(self.get_unique_id_for(context, platform)?)
+        };
+        Ok(AttributeValue::PartyLeader(x))
+    }
 
-fn windows_1252(bytes: &[u8]) -> Option<String> {
-    bytes
-        .iter()
-        .map(|&byte| WINDOWS_1252[u8_usize(byte)])
-        .collect()
+    fn get_attribute_value_pickup(&mut self) -> BitGetResult<AttributeValue> {
+        let has_instigator = self.get_bool()?;
+        let instigator = self.get_option(has_instigator, Self::get_u32)?;
+        let picked_up = self.get_bool()?;
+        Ok(AttributeValue::Pickup {
+            instigator,
+            picked_up,
+        })
+    }
+
+    fn get_attribute_value_pickup_new(&mut self) -> BitGetResult<AttributeValue> {
+        let has_instigator = self.get_bool()?;
+        let instigator = self.get_option(has_instigator, Self::get_u32)?;
+        let picked_up = self.get_u8()?;
+        Ok(AttributeValue::PickupNew {
+            instigator,
+            picked_up,
+        })
+    }
+
+    // The key is always exactly 14 bits wide. It is not a compressed word
+    // and it does not fill a whole u16.
+    fn get_attribute_value_player_history_key(&mut self) -> BitGetResult<AttributeValue> {
+        let x = self.get_bits(14)?;
+        Ok(AttributeValue::PlayerHistoryKey(u32_u16(x)))
+    }
+
+    fn get_attribute_value_rigid_body(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let sleeping = self.get_bool()?;
+        let location = self.get_vector(context)?;
+        let rotation = if context.version >= (868, 22, 7) {
+            self.get_quaternion()?
+        } else {
+            self.get_compressed_rotation()?
+        };
+        let linear_velocity = self.get_option(!sleeping, |this| this.get_vector(context))?;
+        let angular_velocity = self.get_option(!sleeping, |this| this.get_vector(context))?;
+        Ok(AttributeValue::RigidBody {
+            sleeping,
+            location,
+            rotation,
+            linear_velocity,
+            angular_velocity,
+        })
+    }
+
+    fn get_attribute_value_role(&mut self, name: &str) -> BitGetResult<AttributeValue> {
+        let bits = match get_enum_width(name) {
+            Some(bits) => bits,
+            None => return Err(BitGetError::UnknownAttribute(String::from(name))),
+        };
+        let x = self.get_bits(bits)?;
+        Ok(AttributeValue::Role(match x {
+            0 => NetworkRole::None,
+            1 => NetworkRole::SimulatedProxy,
+            2 => NetworkRole::AutonomousProxy,
+            3 => NetworkRole::Authority,
+            _ => NetworkRole::Unknown(x),
+        }))
+    }
+
+    fn get_attribute_value_private_match_settings(&mut self) -> BitGetResult<AttributeValue> {
+        let mutators = self.get_text()?;
+        let joinable_by = self.get_u32()?;
+        let max_players = self.get_u32()?;
+        let game_name = self.get_text()?;
+        let password = self.get_text()?;
+        let flag = self.get_bool()?;
+        Ok(AttributeValue::PrivateMatchSettings {
+            mutators: mutators.trim_end_matches('\0').to_string(),
+            joinable_by,
+            max_players: u32_i32(max_players),
+            game_name: game_name.trim_end_matches('\0').to_string(),
+            password: password.trim_end_matches('\0').to_string(),
+            flag,
+        })
+    }
+
+    fn get_attribute_value_qword(&mut self) -> BitGetResult<AttributeValue> {
+        let x = self.get_u64()?;
+        Ok(AttributeValue::QWord(x))
+    }
+
+    fn get_attribute_value_rep_stat_title(&mut self) -> BitGetResult<AttributeValue> {
+        let unknown = self.get_bool()?;
+        let name = self.get_text()?;
+        let flag = self.get_bool()?;
+        let object_id = self.get_u32()?;
+        let index = self.get_u32()?;
+        Ok(AttributeValue::RepStatTitle {
+            unknown,
+            name: name.trim_end_matches('\0').to_string(),
+            flag,
+            object_id,
+            index,
+        })
+    }
+
+    fn get_attribute_value_reservation(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let number = self.get_u32c(7)?;
+        let unique_id = self.get_unique_id(context)?;
+        let name = self.get_option(unique_id.platform != Platform::Splitscreen, |this| {
+            let x = this.get_text()?;
+            Ok(x.trim_end_matches('\0').to_string())
+        })?;
+        let unknown1 = self.get_bool()?;
+        let unknown2 = self.get_bool()?;
+        let unknown3 = self.get_option(context.version >= (868, 12, 0), |this| {
+            let x = this.get_bits(6)?;
+            Ok(u32_u8(x).unwrap_or(0))
+        })?;
+        Ok(AttributeValue::Reservation {
+            number: number.value,
+            unique_id,
+            name,
+            unknown1,
+            unknown2,
+            unknown3,
+        })
+    }
+
+    fn get_attribute_value_string(&mut self) -> BitGetResult<AttributeValue> {
+        let x = self.get_text()?;
+        Ok(AttributeValue::String(x.trim_end_matches('\0').to_string()))
+    }
+
+    fn get_attribute_value_team_paint(&mut self) -> BitGetResult<AttributeValue> {
+        let team = self.get_u8()?;
+        let primary_color = self.get_u8()?;
+        let accent_color = self.get_u8()?;
+        let primary_finish = self.get_u32()?;
+        let accent_finish = self.get_u32()?;
+        Ok(AttributeValue::TeamPaint {
+            team,
+            primary_color,
+            accent_color,
+            primary_finish,
+            accent_finish,
+        })
+    }
+
+    fn get_attribute_value_unique_id(&mut self, context: &Context) -> BitGetResult<AttributeValue> {
+        let x = self.get_unique_id(context)?;
+        Ok(AttributeValue::UniqueId(x))
+    }
+
+    fn get_attribute_value_welded_info(
+        &mut self,
+        context: &Context,
+    ) -> BitGetResult<AttributeValue> {
+        let actor = self.get_active_actor()?;
+        let offset = self.get_vector(context)?;
+        let mass = self.get_f32()?;
+        let rotation = self.get_rotation()?;
+        Ok(AttributeValue::WeldedInfo {
+            actor,
+            offset,
+            mass,
+            rotation,
+        })
+    }
+
+    fn get_unique_id(&mut self, context: &Context) -> BitGetResult<UniqueId> {
+        let platform = self.get_u8()?;
+        self.get_unique_id_for(context, platform)
+    }
+
+    fn get_unique_id_for(&mut self, context: &Context, platform: u8) -> BitGetResult<UniqueId> {
+        let (platform, remote) = self.get_remote_id(context, platform)?;
+        let local = self.get_u8()?;
+        Ok(UniqueId {
+            platform,
+            remote,
+            local,
+        })
+    }
+
+    fn get_remote_id(
+        &mut self,
+        context: &Context,
+        platform: u8,
+    ) -> BitGetResult<(Platform, RemoteId)> {
+        match platform {
+            0 => {
+                let x = self.get_bits(24)?;
+                Ok((Platform::Splitscreen, RemoteId::Splitscreen(x)))
+            }
+            1 => {
+                let x = self.get_u64()?;
+                Ok((Platform::Steam, RemoteId::Steam(x)))
+            }
+            2 => {
+                let name = self.get_vec(16)?;
+                let name = match windows_1252(&name) {
+                    None => Err(BitGetError::InvalidWindows1252(name)),
+                    Some(x) => Ok(x.trim_end_matches('\0').to_string()),
+                }?;
+                let len = if context.version >= (868, 20, 1) {
+                    16
+                } else {
+                    8
+                };
+                let code = self.get_vec(len)?;
+                Ok((Platform::PlayStation, RemoteId::PlayStation { name, code }))
+            }
+            4 => {
+                let x = self.get_u64()?;
+                Ok((Platform::Xbox, RemoteId::Xbox(x)))
+            }
+            5 => {
+                let x = self.get_u64()?;
+                Ok((Platform::QQ, RemoteId::QQ(x)))
+            }
+            6 => {
+                let x = self.get_vec(32)?;
+                Ok((Platform::Switch, RemoteId::Switch(x)))
+            }
+            7 => {
+                let len = if context.version >= (868, 24, 10) {
+                    8
+                } else {
+                    32
+                };
+                let x = self.get_vec(len)?;
+                // The newest replays follow the PsyNet id with an Epic id,
+                // which is empty for players without one.
+                if context.version < (868, 32, 0) {
+                    return Ok((Platform::PsyNet, RemoteId::PsyNet(x)));
+                }
+                let epic = self.get_text()?;
+                let epic = epic.trim_end_matches('\0').to_string();
+                if epic.is_empty() {
+                    Ok((Platform::PsyNet, RemoteId::PsyNet(x)))
+                } else {
+                    Ok((Platform::PsyNet, RemoteId::PsyNetEpic { psy_net: x, epic }))
+                }
+            }
+            11 => {
+                let x = self.get_text()?;
+                let x = x.trim_end_matches('\0').to_string();
+                Ok((Platform::Epic, RemoteId::Epic(x)))
+            }
+            _ => Err(BitGetError::UnknownPlatform(platform)),
+        }
+    }
+
+    // Each component is a fraction of a half turn, unlike the whole-byte
+    // rotation sent when an actor is spawned.
+    fn get_attribute_value_rotation(&mut self) -> BitGetResult<AttributeValue> {
+        let has_pitch = self.get_bool()?;
+        let pitch = self.get_option(has_pitch, Self::get_compressed_f32)?;
+        let has_yaw = self.get_bool()?;
+        let yaw = self.get_option(has_yaw, Self::get_compressed_f32)?;
+        let has_roll = self.get_bool()?;
+        let roll = self.get_option(has_roll, Self::get_compressed_f32)?;
+        Ok(AttributeValue::Rotation { pitch, yaw, roll })
+    }
+
+    fn get_attribute_value_stat_event(&mut self) -> BitGetResult<AttributeValue> {
+        let unknown = self.get_bool()?;
+        let object_id = self.get_u32()?;
+        Ok(AttributeValue::StatEvent {
+            unknown,
+            object_id: u32_i32(object_id),
+        })
+    }
 }
 
-const WINDOWS_1252: [Option<char>; 256] = [
-    // 0x0_
-    Some('\u{0000}'), // null
-    Some('\u{0001}'), // start of heading
-    Some('\u{0002}'), // start of text
-    Some('\u{0003}'), // end of text
-    Some('\u{0004}'), // end of transmission
-    Some('\u{0005}'), // enquiry
-    Some('\u{0006}'), // acknowledge
-    Some('\u{0007}'), // bell
-    Some('\u{0008}'), // backspace
-    Some('\u{0009}'), // horizontal tabulation
-    Some('\u{000a}'), // line feed
-    Some('\u{000b}'), // vertical tabulation
-    Some('\u{000c}'), // form feed
-    Some('\u{000d}'), // carriage return
-    Some('\u{000e}'), // shift out
-    Some('\u{000f}'), // shift in
-    // 0x1_
-    Some('\u{0010}'), // data link escape
-    Some('\u{0011}'), // device control one
-    Some('\u{0012}'), // device control two
-    Some('\u{0013}'), // device control three
-    Some('\u{0014}'), // device control four
-    Some('\u{0015}'), // negative acknowledge
-    Some('\u{0016}'), // synchronous idle
-    Some('\u{0017}'), // end of transmission block
-    Some('\u{0018}'), // cancel
-    Some('\u{0019}'), // end of medium
-    Some('\u{001a}'), // substitute
-    Some('\u{001b}'), // escape
-    Some('\u{001c}'), // file separator
-    Some('\u{001d}'), // group separator
-    Some('\u{001e}'), // record separator
-    Some('\u{001f}'), // unit separator
-    // 0x2_
-    Some('\u{0020}'), // space
-    Some('\u{0021}'), // exclamation mark
-    Some('\u{0022}'), // quotation mark
-    Some('\u{0023}'), // number sign
-    Some('\u{0024}'), // dollar sign
-    Some('\u{0025}'), // percent sign
-    Some('\u{0026}'), // ampersand
-    Some('\u{0027}'), // apostrophe
-    Some('\u{0028}'), // left parenthesis
-    Some('\u{0029}'), // right parenthesis
-    Some('\u{002a}'), // asterisk
-    Some('\u{002b}'), // plus sign
-    Some('\u{002c}'), // comma
-    Some('\u{002d}'), // hyphen-minus
-    Some('\u{002e}'), // full stop
-    Some('\u{002f}'), // solidus
-    // 0x3_
-    Some('\u{0030}'), // digit zero
-    Some('\u{0031}'), // digit one
-    Some('\u{0032}'), // digit two
-    Some('\u{0033}'), // digit three
-    Some('\u{0034}'), // digit four
-    Some('\u{0035}'), // digit five
-    Some('\u{0036}'), // digit six
-    Some('\u{0037}'), // digit seven
-    Some('\u{0038}'), // digit eight
-    Some('\u{0039}'), // digit nine
-    Some('\u{003a}'), // colon
-    Some('\u{003b}'), // semicolon
-    Some('\u{003c}'), // less-than sign
-    Some('\u{003d}'), // equals sign
-    Some('\u{003e}'), // greater-than sign
-    Some('\u{003f}'), // question mark
-    // 0x4_
-    Some('\u{0040}'), // commercial at
-    Some('\u{0041}'), // latin capital letter a
-    Some('\u{0042}'), // latin capital letter b
-    Some('\u{0043}'), // latin capital letter c
-    Some('\u{0044}'), // latin capital letter d
-    Some('\u{0045}'), // latin capital letter e
-    Some('\u{0046}'), // latin capital letter f
-    Some('\u{0047}'), // latin capital letter g
-    Some('\u{0048}'), // latin capital letter h
-    Some('\u{0049}'), // latin capital letter i
-    Some('\u{004a}'), // latin capital letter j
-    Some('\u{004b}'), // latin capital letter k
-    Some('\u{004c}'), // latin capital letter l
-    Some('\u{004d}'), // latin capital letter m
-    Some('\u{004e}'), // latin capital letter n
-    Some('\u{004f}'), // latin capital letter o
-    // 0x5_
-    Some('\u{0050}'), // latin capital letter p
-    Some('\u{0051}'), // latin capital letter q
-    Some('\u{0052}'), // latin capital letter r
-    Some('\u{0053}'), // latin capital letter s
-    Some('\u{0054}'), // latin capital letter t
-    Some('\u{0055}'), // latin capital letter u
-    Some('\u{0056}'), // latin capital letter v
-    Some('\u{0057}'), // latin capital letter w
-    Some('\u{0058}'), // latin capital letter x
-    Some('\u{0059}'), // latin capital letter y
-    Some('\u{005a}'), // latin capital letter z
-    Some('\u{005b}'), // left square bracket
-    Some('\u{005c}'), // reverse solidus
-    Some('\u{005d}'), // right square bracket
-    Some('\u{005e}'), // circumflex accent
-    Some('\u{005f}'), // low line
-    // 0x6_
-    Some('\u{0060}'), // grave accent
-    Some('\u{0061}'), // latin small letter a
-    Some('\u{0062}'), // latin small letter b
-    Some('\u{0063}'), // latin small letter c
-    Some('\u{0064}'), // latin small letter d
-    Some('\u{0065}'), // latin small letter e
-    Some('\u{0066}'), // latin small letter f
-    Some('\u{0067}'), // latin small letter g
-    Some('\u{0068}'), // latin small letter h
-    Some('\u{0069}'), // latin small letter i
-    Some('\u{006a}'), // latin small letter j
-    Some('\u{006b}'), // latin small letter k
-    Some('\u{006c}'), // latin small letter l
-    Some('\u{006d}'), // latin small letter m
-    Some('\u{006e}'), // latin small letter n
-    Some('\u{006f}'), // latin small letter o
-    // 0x7_
-    Some('\u{0070}'), // latin small letter p
-    Some('\u{0071}'), // latin small letter q
-    Some('\u{0072}'), // latin small letter r
-    Some('\u{0073}'), // latin small letter s
-    Some('\u{0074}'), // latin small letter t
-    Some('\u{0075}'), // latin small letter u
-    Some('\u{0076}'), // latin small letter v
-    Some('\u{0077}'), // latin small letter w
-    Some('\u{0078}'), // latin small letter x
-    Some('\u{0079}'), // latin small letter y
-    Some('\u{007a}'), // latin small letter z
-    Some('\u{007b}'), // left curly bracket
-    Some('\u{007c}'), // vertical line
-    Some('\u{007d}'), // right curly bracket
-    Some('\u{007e}'), // tilde
-    Some('\u{007f}'), // delete
-    // 0x8_
-    Some('\u{20ac}'), // euro sign
-    None,             // undefined
-    Some('\u{201a}'), // single low-9 quotation mark
-    Some('\u{0192}'), // latin small letter f with hook
-    Some('\u{201e}'), // double low-9 quotation mark
-    Some('\u{2026}'), // horizontal ellipsis
-    Some('\u{2020}'), // dagger
-    Some('\u{2021}'), // double dagger
-    Some('\u{02c6}'), // modifier letter circumflex accent
-    Some('\u{2030}'), // per mille sign
-    Some('\u{0160}'), // latin capital letter s with caron
-    Some('\u{2039}'), // single left-pointing angle quotation mark
-    Some('\u{0152}'), // latin capital ligature oe
-    None,             // undefined
-    Some('\u{017d}'), // latin capital letter z with caron
-    None,             // undefined
-    // 0x9_
-    None,             // undefined
-    Some('\u{2018}'), // left single quotation mark
-    Some('\u{2019}'), // right single quotation mark
-    Some('\u{201c}'), // left double quotation mark
-    Some('\u{201d}'), // right double quotation mark
-    Some('\u{2022}'), // bullet
-    Some('\u{2013}'), // en dash
-    Some('\u{2014}'), // em dash
-    Some('\u{02dc}'), // small tilde
-    Some('\u{2122}'), // trade mark sign
-    Some('\u{0161}'), // latin small letter s with caron
-    Some('\u{203a}'), // single right-pointing angle quotation mark
-    Some('\u{0153}'), // latin small ligature oe
-    None,             // undefined
-    Some('\u{017e}'), // latin small letter z with caron
-    Some('\u{0178}'), // latin capital letter y with diaeresis
-    // 0xa_
-    Some('\u{00a0}'), // no-break space
+impl Get<'_> {
+    fn get_f32(&mut self) -> GetResult<f32> {
+        let x = self.get_u32()?;
+        Ok(u32_f32(x))
+    }
+
+    fn get_i32(&mut self) -> GetResult<i32> {
+        let x = self.get_u32()?;
+        Ok(u32_i32(x))
+    }
+
+    fn get_option<F, T>(&mut self, condition: bool, get_value: F) -> GetResult<Option<T>>
+    where
+        F: Fn(&mut Self) -> GetResult<T>,
+    {
+        if condition {
+            let value = get_value(self)?;
+            Ok(Some(value))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_u8(&mut self) -> GetResult<u8> {
+        let x = self.get_vec(1)?;
+        Ok(x[0])
+    }
+
+    fn get_u16(&mut self) -> GetResult<u16> {
+        let lower = self.get_u8()?;
+        let upper = self.get_u8()?;
+        Ok(u8_u16(lower) | u8_u16(upper) << 8)
+    }
+
+    fn get_u32(&mut self) -> GetResult<u32> {
+        let lower = self.get_u16()?;
+        let upper = self.get_u16()?;
+        Ok(u16_u32(lower) | u16_u32(upper) << 16)
+    }
+
+    fn get_u64(&mut self) -> GetResult<u64> {
+        let lower = self.get_u32()?;
+        let upper = self.get_u32()?;
+        Ok(u32_u64(lower) | u32_u64(upper) << 32)
+    }
+
+    fn get_vec(&mut self, len: usize) -> GetResult<Vec<u8>> {
+        let bytes = self.peek_vec(len)?;
+        self.index += len;
+        Ok(bytes)
+    }
+
+    fn peek_vec(&self, len: usize) -> GetResult<Vec<u8>> {
+        let bytes = self.peek_slice(len)?;
+        Ok(bytes.to_vec())
+    }
+
+    fn peek_slice(&self, len: usize) -> GetResult<&[u8]> {
+        let index = self.index + len;
+        match self.bytes.get(self.index..index) {
+            None => Err(GetError::IndexOutOfBounds {
+                index,
+                len: self.bytes.len(),
+            }),
+            Some(bytes) => Ok(bytes),
+        }
+    }
+}
+
+impl BitGet {
+    fn get_bit_index(&self) -> usize {
+        8 * self.byte_index + self.bit_index
+    }
+
+    fn get_byte(&self, index: usize) -> BitGetResult<u8> {
+        match self.bytes.get(index) {
+            None => Err(BitGetError::IndexOutOfBounds {
+                index,
+                len: self.bytes.len(),
+            }),
+            Some(byte) => Ok(*byte),
+        }
+    }
+
+    fn get_bool(&mut self) -> BitGetResult<bool> {
+        let byte = self.get_byte(self.byte_index)?;
+        let bit = byte & 1 << self.bit_index != 0;
+        self.bit_index += 1;
+        if self.bit_index == 8 {
+            self.bit_index = 0;
+            self.byte_index += 1;
+        }
+        Ok(bit)
+    }
+
+    fn get_bits(&mut self, count: u32) -> BitGetResult<u32> {
+        if count > 32 {
+            return Err(BitGetError::TooManyBits(count));
+        }
+        let mut value = 0;
+        for index in 0..count {
+            let bit = self.get_bool()?;
+            if bit {
+                value |= 1 << index;
+            }
+        }
+        Ok(value)
+    }
+
+    fn peek_bits(&mut self, count: u32) -> BitGetResult<u32> {
+        let byte_index = self.byte_index;
+        let bit_index = self.bit_index;
+        let bits = self.get_bits(count);
+        self.byte_index = byte_index;
+        self.bit_index = bit_index;
+        bits
+    }
+
+    fn get_f32(&mut self) -> BitGetResult<f32> {
+        let x = self.get_u32()?;
+        Ok(u32_f32(x))
+    }
+
+    fn get_i8(&mut self) -> BitGetResult<i8> {
+        let x = self.get_u8()?;
+        Ok(u8_i8(x))
+    }
+
+    fn get_option<F, T>(&mut self, condition: bool, get_value: F) -> BitGetResult<Option<T>>
+    where
+        F: Fn(&mut Self) -> BitGetResult<T>,
+    {
+        if condition {
+            let value = get_value(self)?;
+            Ok(Some(value))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Reads whole bytes rather than eight separate bits. When the stream is
+    // not byte aligned the result straddles two bytes.
+    fn get_u8(&mut self) -> BitGetResult<u8> {
+        let lower = self.get_byte(self.byte_index)?;
+        let byte = if self.bit_index == 0 {
+            lower
+        } else {
+            let upper = self.get_byte(self.byte_index + 1)?;
+            lower >> self.bit_index | upper << (8 - self.bit_index)
+        };
+        self.byte_index += 1;
+        Ok(byte)
+    }
+
+    fn get_u16(&mut self) -> BitGetResult<u16> {
+        let lower = self.get_u8()?;
+        let upper = self.get_u8()?;
+        Ok(u8_u16(lower) | u8_u16(upper) << 8)
+    }
+
+    fn get_u32(&mut self) -> BitGetResult<u32> {
+        let lower = self.get_u16()?;
+        let upper = self.get_u16()?;
+        Ok(u16_u32(lower) | u16_u32(upper) << 16)
+    }
+
+    fn get_u64(&mut self) -> BitGetResult<u64> {
+        let lower = self.get_u32()?;
+        let upper = self.get_u32()?;
+        Ok(u32_u64(lower) | u32_u64(upper) << 32)
+    }
+
+    fn get_vec(&mut self, len: usize) -> BitGetResult<Vec<u8>> {
+        if self.bit_index == 0 {
+            let end = self.byte_index.saturating_add(len);
+            return match self.bytes.get(self.byte_index..end) {
+                None => Err(BitGetError::IndexOutOfBounds {
+                    index: self.bytes.len().max(self.byte_index),
+                    len: self.bytes.len(),
+                }),
+                Some(bytes) => {
+                    self.byte_index = end;
+                    Ok(bytes.to_vec())
+                }
+            };
+        }
+        // The length comes from the stream, so check it before allocating.
+        if len > self.bytes.len().saturating_sub(self.byte_index) {
+            return Err(BitGetError::SizeOutOfRange(u64_i64(usize_u64(len))));
+        }
+        let mut bytes = Vec::with_capacity(len);
+        for _ in 0..len {
+            let byte = self.get_u8()?;
+            bytes.push(byte)
+        }
+        Ok(bytes)
+    }
+
+    fn get_text(&mut self) -> BitGetResult<String> {
+        let size = u32_i32(self.get_u32()?);
+        if size < 0 {
+            let len = match size.checked_mul(-2).and_then(i32_usize) {
+                None => Err(BitGetError::SizeOutOfRange(i64::from(size))),
+                Some(len) => Ok(len),
+            }?;
+            let bytes = self.get_vec(len)?;
+            match utf_16(&bytes) {
+                Err(_) => Err(BitGetError::InvalidUtf16(bytes)),
+                Ok(value) => Ok(value),
+            }
+        } else {
+            let len = match i32_usize(size) {
+                None => Err(BitGetError::SizeOutOfRange(i64::from(size))),
+                Some(len) => Ok(len),
+            }?;
+            let bytes = self.get_vec(len)?;
+            match windows_1252(&bytes) {
+                None => Err(BitGetError::InvalidWindows1252(bytes)),
+                Some(value) => Ok(value),
+            }
+        }
+    }
+}
+
+pub type AttributeDecoder = fn(&mut BitReader) -> Result<AttributeValue, BitGetError>;
+
+pub struct BitReader<'a> {
+    bit_get: &'a mut BitGet,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn get_bits(&mut self, count: u32) -> Result<u32, BitGetError> {
+        self.bit_get.get_bits(count)
+    }
+
+    pub fn peek_bits(&mut self, count: u32) -> Result<u32, BitGetError> {
+        self.bit_get.peek_bits(count)
+    }
+
+    pub fn get_bool(&mut self) -> Result<bool, BitGetError> {
+        self.bit_get.get_bool()
+    }
+
+    pub fn get_f32(&mut self) -> Result<f32, BitGetError> {
+        self.bit_get.get_f32()
+    }
+
+    pub fn get_u8(&mut self) -> Result<u8, BitGetError> {
+        self.bit_get.get_u8()
+    }
+
+    pub fn get_u32(&mut self) -> Result<u32, BitGetError> {
+        self.bit_get.get_u32()
+    }
+
+    pub fn get_u32c(&mut self, limit: u32) -> Result<u32, BitGetError> {
+        let x = self.bit_get.get_u32c(limit)?;
+        Ok(x.value)
+    }
+}
+
+fn i32_f32(x: i32) -> f32 {
+    x as f32
+}
+
+fn i32_usize(x: i32) -> Option<usize> {
+    usize::try_from(x).ok()
+}
+
+fn u8_i8(x: u8) -> i8 {
+    x as i8
+}
+
+fn u8_u16(x: u8) -> u16 {
+    u16::from(x)
+}
+
+fn u8_usize(x: u8) -> usize {
+    x as usize
+}
+
+fn u16_u32(x: u16) -> u32 {
+    u32::from(x)
+}
+
+fn u32_f32(x: u32) -> f32 {
+    f32::from_bits(x)
+}
+
+fn u32_i32(x: u32) -> i32 {
+    x as i32
+}
+
+fn u32_u16(x: u32) -> u16 {
+    x as u16
+}
+
+fn u32_u8(x: u32) -> Option<u8> {
+    u8::try_from(x).ok()
+}
+
+fn u32_u64(x: u32) -> u64 {
+    u64::from(x)
+}
+
+fn u32_usize(x: u32) -> Option<usize> {
+    usize::try_from(x).ok()
+}
+
+fn u64_i64(x: u64) -> i64 {
+    x as i64
+}
+
+fn u64_usize(x: u64) -> Option<usize> {
+    usize::try_from(x).ok()
+}
+
+fn get_size(size: u32) -> GetResult<usize> {
+    match u32_usize(size) {
+        None => Err(GetError::SizeOutOfRange(i64::from(size))),
+        Some(x) => Ok(x),
+    }
+}
+
+fn usize_u32(x: usize) -> Option<u32> {
+    u32::try_from(x).ok()
+}
+
+fn usize_u64(x: usize) -> u64 {
+    x as u64
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Self { crc: 0x1034_0dfe }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.crc = bytes.iter().fold(self.crc, |crc, byte| {
+            crc << 8 ^ CRC_32[u8_usize(byte ^ crc.to_be_bytes()[0])]
+        })
+    }
+
+    pub fn finalize(self) -> u32 {
+        !self.crc
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn crc_32(bytes: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(bytes);
+    crc.finalize()
+}
+
+const CRC_32: [u32; 256] = [
+    0x0000_0000,
+    0x04c1_1db7,
+    0x0982_3b6e,
+    0x0d43_26d9,
+    0x1304_76dc,
+    0x17c5_6b6b,
+    0x1a86_4db2,
+    0x1e47_5005,
+    0x2608_edb8,
+    0x22c9_f00f,
+    0x2f8a_d6d6,
+    0x2b4b_cb61,
+    0x350c_9b64,
+    0x31cd_86d3,
+    0x3c8e_a00a,
+    0x384f_bdbd,
+    0x4c11_db70,
+    0x48d0_c6c7,
+    0x4593_e01e,
+    0x4152_fda9,
+    0x5f15_adac,
+    0x5bd4_b01b,
+    0x5697_96c2,
+    0x5256_8b75,
+    0x6a19_36c8,
+    0x6ed8_2b7f,
+    0x639b_0da6,
+    0x675a_1011,
+    0x791d_4014,
+    0x7ddc_5da3,
+    0x709f_7b7a,
+    0x745e_66cd,
+    0x9823_b6e0,
+    0x9ce2_ab57,
+    0x91a1_8d8e,
+    0x9560_9039,
+    0x8b27_c03c,
+    0x8fe6_dd8b,
+    0x82a5_fb52,
+    0x8664_e6e5,
+    0xbe2b_5b58,
+    0xbaea_46ef,
+    0xb7a9_6036,
+    0xb368_7d81,
+    0xad2f_2d84,
+    0xa9ee_3033,
+    0xa4ad_16ea,
+    0xa06c_0b5d,
+    0xd432_6d90,
+    0xd0f3_7027,
+    0xddb0_56fe,
+    0xd971_4b49,
+    0xc736_1b4c,
+    0xc3f7_06fb,
+    0xceb4_2022,
+    0xca75_3d95,
+    0xf23a_8028,
+    0xf6fb_9d9f,
+    0xfbb8_bb46,
+    0xff79_a6f1,
+    0xe13e_f6f4,
+    0xe5ff_eb43,
+    0xe8bc_cd9a,
+    0xec7d_d02d,
+    0x3486_7077,
+    0x3047_6dc0,
+    0x3d04_4b19,
+    0x39c5_56ae,
+    0x2782_06ab,
+    0x2343_1b1c,
+    0x2e00_3dc5,
+    0x2ac1_2072,
+    0x128e_9dcf,
+    0x164f_8078,
+    0x1b0c_a6a1,
+    0x1fcd_bb16,
+    0x018a_eb13,
+    0x054b_f6a4,
+    0x0808_d07d,
+    0x0cc9_cdca,
+    0x7897_ab07,
+    0x7c56_b6b0,
+    0x7115_9069,
+    0x75d4_8dde,
+    0x6b93_dddb,
+    0x6f52_c06c,
+    0x6211_e6b5,
+    0x66d0_fb02,
+    0x5e9f_46bf,
+    0x5a5e_5b08,
+    0x571d_7dd1,
+    0x53dc_6066,
+    0x4d9b_3063,
+    0x495a_2dd4,
+    0x4419_0b0d,
+    0x40d8_16ba,
+    0xaca5_c697,
+    0xa864_db20,
+    0xa527_fdf9,
+    0xa1e6_e04e,
+    0xbfa1_b04b,
+    0xbb60_adfc,
+    0xb623_8b25,
+    0xb2e2_9692,
+    0x8aad_2b2f,
+    0x8e6c_3698,
+    0x832f_1041,
+    0x87ee_0df6,
+    0x99a9_5df3,
+    0x9d68_4044,
+    0x902b_669d,
+    0x94ea_7b2a,
+    0xe0b4_1de7,
+    0xe475_0050,
+    0xe936_2689,
+    0xedf7_3b3e,
+    0xf3b0_6b3b,
+    0xf771_768c,
+    0xfa32_5055,
+    0xfef3_4de2,
+    0xc6bc_f05f,
+    0xc27d_ede8,
+    0xcf3e_cb31,
+    0xcbff_d686,
+    0xd5b8_8683,
+    0xd179_9b34,
+    0xdc3a_bded,
+    0xd8fb_a05a,
+    0x690c_e0ee,
+    0x6dcd_fd59,
+    0x608e_db80,
+    0x644f_c637,
+    0x7a08_9632,
+    0x7ec9_8b85,
+    0x738a_ad5c,
+    0x774b_b0eb,
+    0x4f04_0d56,
+    0x4bc5_10e1,
+    0x4686_3638,
+    0x4247_2b8f,
+    0x5c00_7b8a,
+    0x58c1_663d,
+    0x5582_40e4,
+    0x5143_5d53,
+    0x251d_3b9e,
+    0x21dc_2629,
+    0x2c9f_00f0,
+    0x285e_1d47,
+    0x3619_4d42,
+    0x32d8_50f5,
+    0x3f9b_762c,
+    0x3b5a_6b9b,
+    0x0315_d626,
+    0x07d4_cb91,
+    0x0a97_ed48,
+    0x0e56_f0ff,
+    0x1011_a0fa,
+    0x14d0_bd4d,
+    0x1993_9b94,
+    0x1d52_8623,
+    0xf12f_560e,
+    0xf5ee_4bb9,
+    0xf8ad_6d60,
+    0xfc6c_70d7,
+    0xe22b_20d2,
+    0xe6ea_3d65,
+    0xeba9_1bbc,
+    0xef68_060b,
+    0xd727_bbb6,
+    0xd3e6_a601,
+    0xdea5_80d8,
+    0xda64_9d6f,
+    0xc423_cd6a,
+    0xc0e2_d0dd,
+    0xcda1_f604,
+    0xc960_ebb3,
+    0xbd3e_8d7e,
+    0xb9ff_90c9,
+    0xb4bc_b610,
+    0xb07d_aba7,
+    0xae3a_fba2,
+    0xaafb_e615,
+    0xa7b8_c0cc,
+    0xa379_dd7b,
+    0x9b36_60c6,
+    0x9ff7_7d71,
+    0x92b4_5ba8,
+    0x9675_461f,
+    0x8832_161a,
+    0x8cf3_0bad,
+    0x81b0_2d74,
+    0x8571_30c3,
+    0x5d8a_9099,
+    0x594b_8d2e,
+    0x5408_abf7,
+    0x50c9_b640,
+    0x4e8e_e645,
+    0x4a4f_fbf2,
+    0x470c_dd2b,
+    0x43cd_c09c,
+    0x7b82_7d21,
+    0x7f43_6096,
+    0x7200_464f,
+    0x76c1_5bf8,
+    0x6886_0bfd,
+    0x6c47_164a,
+    0x6104_3093,
+    0x65c5_2d24,
+    0x119b_4be9,
+    0x155a_565e,
+    0x1819_7087,
+    0x1cd8_6d30,
+    0x029f_3d35,
+    0x065e_2082,
+    0x0b1d_065b,
+    0x0fdc_1bec,
+    0x3793_a651,
+    0x3352_bbe6,
+    0x3e11_9d3f,
+    0x3ad0_8088,
+    0x2497_d08d,
+    0x2056_cd3a,
+    0x2d15_ebe3,
+    0x29d4_f654,
+    0xc5a9_2679,
+    0xc168_3bce,
+    0xcc2b_1d17,
+    0xc8ea_00a0,
+    0xd6ad_50a5,
+    0xd26c_4d12,
+    0xdf2f_6bcb,
+    0xdbee_767c,
+    0xe3a1_cbc1,
+    0xe760_d676,
+    0xea23_f0af,
+    0xeee2_ed18,
+    0xf0a5_bd1d,
+    0xf464_a0aa,
+    0xf927_8673,
+    0xfde6_9bc4,
+    0x89b8_fd09,
+    0x8d79_e0be,
+    0x803a_c667,
+    0x84fb_dbd0,
+    0x9abc_8bd5,
+    0x9e7d_9662,
+    0x933e_b0bb,
+    0x97ff_ad0c,
+    0xafb0_10b1,
+    0xab71_0d06,
+    0xa632_2bdf,
+    0xa2f3_3668,
+    0xbcb4_666d,
+    0xb875_7bda,
+    0xb536_5d03,
+    0xb1f7_40b4,
+];
+
+fn windows_1252(bytes: &[u8]) -> Option<String> {
+    bytes
+        .iter()
+        .map(|&byte| WINDOWS_1252[u8_usize(byte)])
+        .collect()
+}
+
+const WINDOWS_1252: [Option<char>; 256] = [
+    // 0x0_
+    Some('\u{0000}'), // null
+    Some('\u{0001}'), // start of heading
+    Some('\u{0002}'), // start of text
+    Some('\u{0003}'), // end of text
+    Some('\u{0004}'), // end of transmission
+    Some('\u{0005}'), // enquiry
+    Some('\u{0006}'), // acknowledge
+    Some('\u{0007}'), // bell
+    Some('\u{0008}'), // backspace
+    Some('\u{0009}'), // horizontal tabulation
+    Some('\u{000a}'), // line feed
+    Some('\u{000b}'), // vertical tabulation
+    Some('\u{000c}'), // form feed
+    Some('\u{000d}'), // carriage return
+    Some('\u{000e}'), // shift out
+    Some('\u{000f}'), // shift in
+    // 0x1_
+    Some('\u{0010}'), // data link escape
+    Some('\u{0011}'), // device control one
+    Some('\u{0012}'), // device control two
+    Some('\u{0013}'), // device control three
+    Some('\u{0014}'), // device control four
+    Some('\u{0015}'), // negative acknowledge
+    Some('\u{0016}'), // synchronous idle
+    Some('\u{0017}'), // end of transmission block
+    Some('\u{0018}'), // cancel
+    Some('\u{0019}'), // end of medium
+    Some('\u{001a}'), // substitute
+    Some('\u{001b}'), // escape
+    Some('\u{001c}'), // file separator
+    Some('\u{001d}'), // group separator
+    Some('\u{001e}'), // record separator
+    Some('\u{001f}'), // unit separator
+    // 0x2_
+    Some('\u{0020}'), // space
+    Some('\u{0021}'), // exclamation mark
+    Some('\u{0022}'), // quotation mark
+    Some('\u{0023}'), // number sign
+    Some('\u{0024}'), // dollar sign
+    Some('\u{0025}'), // percent sign
+    Some('\u{0026}'), // ampersand
+    Some('\u{0027}'), // apostrophe
+    Some('\u{0028}'), // left parenthesis
+    Some('\u{0029}'), // right parenthesis
+    Some('\u{002a}'), // asterisk
+    Some('\u{002b}'), // plus sign
+    Some('\u{002c}'), // comma
+    Some('\u{002d}'), // hyphen-minus
+    Some('\u{002e}'), // full stop
+    Some('\u{002f}'), // solidus
+    // 0x3_
+    Some('\u{0030}'), // digit zero
+    Some('\u{0031}'), // digit one
+    Some('\u{0032}'), // digit two
+    Some('\u{0033}'), // digit three
+    Some('\u{0034}'), // digit four
+    Some('\u{0035}'), // digit five
+    Some('\u{0036}'), // digit six
+    Some('\u{0037}'), // digit seven
+    Some('\u{0038}'), // digit eight
+    Some('\u{0039}'), // digit nine
+    Some('\u{003a}'), // colon
+    Some('\u{003b}'), // semicolon
+    Some('\u{003c}'), // less-than sign
+    Some('\u{003d}'), // equals sign
+    Some('\u{003e}'), // greater-than sign
+    Some('\u{003f}'), // question mark
+    // 0x4_
+    Some('\u{0040}'), // commercial at
+    Some('\u{0041}'), // latin capital letter a
+    Some('\u{0042}'), // latin capital letter b
+    Some('\u{0043}'), // latin capital letter c
+    Some('\u{0044}'), // latin capital letter d
+    Some('\u{0045}'), // latin capital letter e
+    Some('\u{0046}'), // latin capital letter f
+    Some('\u{0047}'), // latin capital letter g
+    Some('\u{0048}'), // latin capital letter h
+    Some('\u{0049}'), // latin capital letter i
+    Some('\u{004a}'), // latin capital letter j
+    Some('\u{004b}'), // latin capital letter k
+    Some('\u{004c}'), // latin capital letter l
+    Some('\u{004d}'), // latin capital letter m
+    Some('\u{004e}'), // latin capital letter n
+    Some('\u{004f}'), // latin capital letter o
+    // 0x5_
+    Some('\u{0050}'), // latin capital letter p
+    Some('\u{0051}'), // latin capital letter q
+    Some('\u{0052}'), // latin capital letter r
+    Some('\u{0053}'), // latin capital letter s
+    Some('\u{0054}'), // latin capital letter t
+    Some('\u{0055}'), // latin capital letter u
+    Some('\u{0056}'), // latin capital letter v
+    Some('\u{0057}'), // latin capital letter w
+    Some('\u{0058}'), // latin capital letter x
+    Some('\u{0059}'), // latin capital letter y
+    Some('\u{005a}'), // latin capital letter z
+    Some('\u{005b}'), // left square bracket
+    Some('\u{005c}'), // reverse solidus
+    Some('\u{005d}'), // right square bracket
+    Some('\u{005e}'), // circumflex accent
+    Some('\u{005f}'), // low line
+    // 0x6_
+    Some('\u{0060}'), // grave accent
+    Some('\u{0061}'), // latin small letter a
+    Some('\u{0062}'), // latin small letter b
+    Some('\u{0063}'), // latin small letter c
+    Some('\u{0064}'), // latin small letter d
+    Some('\u{0065}'), // latin small letter e
+    Some('\u{0066}'), // latin small letter f
+    Some('\u{0067}'), // latin small letter g
+    Some('\u{0068}'), // latin small letter h
+    Some('\u{0069}'), // latin small letter i
+    Some('\u{006a}'), // latin small letter j
+    Some('\u{006b}'), // latin small letter k
+    Some('\u{006c}'), // latin small letter l
+    Some('\u{006d}'), // latin small letter m
+    Some('\u{006e}'), // latin small letter n
+    Some('\u{006f}'), // latin small letter o
+    // 0x7_
+    Some('\u{0070}'), // latin small letter p
+    Some('\u{0071}'), // latin small letter q
+    Some('\u{0072}'), // latin small letter r
+    Some('\u{0073}'), // latin small letter s
+    Some('\u{0074}'), // latin small letter t
+    Some('\u{0075}'), // latin small letter u
+    Some('\u{0076}'), // latin small letter v
+    Some('\u{0077}'), // latin small letter w
+    Some('\u{0078}'), // latin small letter x
+    Some('\u{0079}'), // latin small letter y
+    Some('\u{007a}'), // latin small letter z
+    Some('\u{007b}'), // left curly bracket
+    Some('\u{007c}'), // vertical line
+    Some('\u{007d}'), // right curly bracket
+    Some('\u{007e}'), // tilde
+    Some('\u{007f}'), // delete
+    // 0x8_
+    Some('\u{20ac}'), // euro sign
+    None,             // undefined
+    Some('\u{201a}'), // single low-9 quotation mark
+    Some('\u{0192}'), // latin small letter f with hook
+    Some('\u{201e}'), // double low-9 quotation mark
+    Some('\u{2026}'), // horizontal ellipsis
+    Some('\u{2020}'), // dagger
+    Some('\u{2021}'), // double dagger
+    Some('\u{02c6}'), // modifier letter circumflex accent
+    Some('\u{2030}'), // per mille sign
+    Some('\u{0160}'), // latin capital letter s with caron
+    Some('\u{2039}'), // single left-pointing angle quotation mark
+    Some('\u{0152}'), // latin capital ligature oe
+    None,             // undefined
+    Some('\u{017d}'), // latin capital letter z with caron
+    None,             // undefined
+    // 0x9_
+    None,             // undefined
+    Some('\u{2018}'), // left single quotation mark
+    Some('\u{2019}'), // right single quotation mark
+    Some('\u{201c}'), // left double quotation mark
+    Some('\u{201d}'), // right double quotation mark
+    Some('\u{2022}'), // bullet
+    Some('\u{2013}'), // en dash
+    Some('\u{2014}'), // em dash
+    Some('\u{02dc}'), // small tilde
+    Some('\u{2122}'), // trade mark sign
+    Some('\u{0161}'), // latin small letter s with caron
+    Some('\u{203a}'), // single right-pointing angle quotation mark
+    Some('\u{0153}'), // latin small ligature oe
+    None,             // undefined
+    Some('\u{017e}'), // latin small letter z with caron
+    Some('\u{0178}'), // latin capital letter y with diaeresis
+    // 0xa_
+    Some('\u{00a0}'), // no-break space
     Some('\u{00a1}'), // inverted exclamation mark
     Some('\u{00a2}'), // cent sign
     Some('\u{00a3}'), // pound sign
@@ -1678,17 +4476,2953 @@ const WINDOWS_1252: [Option<char>; 256] = [
     Some('\u{00ff}'), // latin small letter y with diaeresis
 ];
 
-fn utf_16(bytes: &[u8]) -> Option<String> {
-    let mut units = Vec::with_capacity(bytes.len() / 2);
-    for chunk in bytes.chunks(2) {
-        let lower = u8_u16(chunk[0]);
-        match chunk.get(1) {
-            Some(&upper) => units.push(lower | u8_u16(upper) << 8),
-            None => return None,
+enum Utf16Error {
+    InvalidUnits,
+    OddLength,
+}
+
+fn utf_16(bytes: &[u8]) -> Result<String, Utf16Error> {
+    let mut units = Vec::with_capacity(bytes.len() / 2);
+    for chunk in bytes.chunks(2) {
+        let lower = u8_u16(chunk[0]);
+        match chunk.get(1) {
+            Some(&upper) => units.push(lower | u8_u16(upper) << 8),
+            None => return Err(Utf16Error::OddLength),
+        }
+    }
+    match String::from_utf16(&units) {
+        Ok(string) => Ok(string),
+        Err(_) => Err(Utf16Error::InvalidUnits),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bits are listed in the order they are read, least significant first.
+    fn bit_get(bits: &str) -> BitGet {
+        let mut bytes = vec![0; bits.len().div_ceil(8)];
+        for (index, bit) in bits.chars().enumerate() {
+            if bit == '1' {
+                bytes[index / 8] |= 1 << (index % 8);
+            }
+        }
+        BitGet::new(bytes)
+    }
+
+    fn text(value: &str) -> Text {
+        let value = format!("{}\0", value);
+        Text {
+            size: usize_u32(value.len()).map_or(0, u32_i32),
+            value,
+        }
+    }
+
+    fn header(properties: Vec<(&str, PropertyValue)>) -> Header {
+        Header {
+            version: Version {
+                major: 868,
+                minor: 22,
+                patch: Some(7),
+            },
+            label: text("TAGame.Replay_Soccar_TA"),
+            properties: Dictionary {
+                value: properties
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let property = Property {
+                            label: text("IntProperty"),
+                            size: 4,
+                            value,
+                        };
+                        (text(key), property)
+                    })
+                    .collect(),
+                last: text("None"),
+            },
+        }
+    }
+
+    fn list<T>(value: Vec<T>) -> List<T> {
+        List {
+            size: usize_u32(value.len()).unwrap_or(0),
+            value,
+        }
+    }
+
+    fn content(objects: &[&str], frames: Vec<Frame>) -> Content {
+        Content {
+            levels: list(Vec::new()),
+            keyframes: list(Vec::new()),
+            size: 0,
+            messages: list(Vec::new()),
+            marks: list(Vec::new()),
+            packages: list(Vec::new()),
+            objects: list(objects.iter().map(|object| text(object)).collect()),
+            names: list(Vec::new()),
+            classes: list(Vec::new()),
+            caches: list(Vec::new()),
+            frames,
+            consumed_bits: 0,
+        }
+    }
+
+    fn replication(actor: u32, value: ReplicationValue) -> Replication {
+        Replication {
+            actor: U32C {
+                limit: 1023,
+                value: actor,
+            },
+            value,
+        }
+    }
+
+    fn created(class: &str) -> ReplicationValue {
+        ReplicationValue::Created {
+            unknown: false,
+            name_index: None,
+            name: None,
+            object_index: 0,
+            object: String::new(),
+            class_id: 0,
+            class: format!("{}\0", class),
+            location: None,
+            rotation: None,
+        }
+    }
+
+    fn updated(object: &str, value: AttributeValue) -> ReplicationValue {
+        ReplicationValue::Updated(vec![Attribute {
+            class_id: 0,
+            class: String::new(),
+            stream_id: U32C { limit: 0, value: 0 },
+            object_id: 0,
+            object: format!("{}\0", object),
+            value,
+        }])
+    }
+
+    fn bits(value: u32, width: u32) -> String {
+        (0..width)
+            .map(|index| if value & 1 << index == 0 { '0' } else { '1' })
+            .collect()
+    }
+
+    fn bits_u32c(value: u32, limit: u32) -> String {
+        let mut written = String::new();
+        let mut sum = 0;
+        let max_index = 32 - limit.leading_zeros();
+        for index in 0..max_index {
+            let step = 1 << index;
+            if sum + step > limit {
+                break;
+            }
+            if value & step == 0 {
+                written.push('0');
+            } else {
+                written.push('1');
+                sum += step;
+            }
+        }
+        written
+    }
+
+    // Classes and attributes are given by object index, the same way the
+    // class and cache lists refer to them.
+    fn context(objects: &[&str], classes: &[u32], attributes: &[(u32, u32, u32)]) -> Context {
+        let mut class_attributes: HashMap<u32, BTreeMap<u32, u32>> = HashMap::new();
+        for &(class, stream_id, object) in attributes {
+            class_attributes
+                .entry(class)
+                .or_default()
+                .insert(stream_id, object);
+        }
+        Context {
+            num_frames: 0,
+            frame: 0,
+            max_channels: 1023,
+            version: (868, 22, 7),
+            names: vec![String::from("Name\0")],
+            objects: objects
+                .iter()
+                .map(|object| format!("{}\0", object))
+                .collect(),
+            classes: classes
+                .iter()
+                .map(|&class| (class, format!("{}\0", objects[u32_usize(class).unwrap()])))
+                .collect(),
+            classes_with_location: Context::get_classes_with_location(),
+            classes_with_rotation: Context::get_classes_with_rotation(),
+            actors: HashMap::new(),
+            attributes: class_attributes,
+            options: Options::new(),
+        }
+    }
+
+    #[test]
+    fn get_u32c_matches_reference_serialization() {
+        let max_channels = Context::get_max_channels(&header(Vec::new()));
+        // (limit, bits, value)
+        let cases = [
+            (1, "0", 0),
+            (1, "1", 1),
+            (2, "00", 0),
+            (2, "1", 1),
+            (2, "01", 2),
+            (3, "00", 0),
+            (3, "10", 1),
+            (3, "01", 2),
+            (3, "11", 3),
+            (1023, "0000000000", 0),
+            (1023, "1000000001", 513),
+            (1023, "1111111111", 1023),
+            (1024, "00000000000", 0),
+            (1024, "00000000001", 1024),
+            (1024, "1000000000", 1),
+            (1024, "1111111111", 1023),
+            (max_channels, "0000000000", 0),
+            (max_channels, "0100000000", 2),
+            (max_channels, "1111111111", 1023),
+        ];
+        for &(limit, bits, value) in &cases {
+            let mut bit_get = bit_get(bits);
+            let actual = bit_get.get_u32c(limit).unwrap();
+            assert_eq!(
+                actual,
+                U32C { limit, value },
+                "limit {} bits {}",
+                limit,
+                bits
+            );
+            assert_eq!(
+                bit_get.get_bit_index(),
+                bits.len(),
+                "limit {} bits {}",
+                limit,
+                bits
+            );
+        }
+    }
+
+    #[test]
+    fn get_u32c_reads_full_width_of_zero() {
+        let max_channels = Context::get_max_channels(&header(Vec::new()));
+        // (limit, bits read for a value of zero)
+        let cases = [
+            (1, 1),
+            (2, 2),
+            (3, 2),
+            (4, 3),
+            (1023, 10),
+            (1024, 11),
+            (1025, 11),
+            (max_channels, 10),
+        ];
+        for &(limit, width) in &cases {
+            let mut bit_get = BitGet::new(vec![0; 4]);
+            assert_eq!(bit_get.get_u32c(limit).unwrap().value, 0);
+            assert_eq!(bit_get.get_bit_index(), width, "limit {}", limit);
+        }
+    }
+
+    #[test]
+    fn get_replication_forgets_destroyed_actors() {
+        let mut context = context(
+            &[
+                "TAGame.Ball_Example_TA",
+                "Archetypes.Ball.Example",
+                "TAGame.Car_Example_TA",
+                "Archetypes.Car.Example",
+                "Engine.Actor:bHidden",
+            ],
+            &[0, 2],
+            &[(2, 1, 4)],
+        );
+        let actor = bits_u32c(5, 1023);
+        let create = |object| format!("{}110{}{}", actor, bits(0, 32), bits(object, 32));
+        let destroy = format!("{}0", actor);
+        let update = format!("{}101{}10", actor, bits_u32c(1, 1));
+        let stream = format!("{}{}{}{}", create(1), destroy, create(3), update);
+        let mut bit_get = bit_get(&stream);
+
+        bit_get.get_replication(&mut context).unwrap();
+        assert_eq!(context.actors.get(&5), Some(&0));
+        bit_get.get_replication(&mut context).unwrap();
+        assert_eq!(context.actors.get(&5), None);
+        bit_get.get_replication(&mut context).unwrap();
+        assert_eq!(context.actors.get(&5), Some(&2));
+        let replication = bit_get.get_replication(&mut context).unwrap();
+        match replication.value {
+            ReplicationValue::Updated(attributes) => {
+                assert_eq!(attributes.len(), 1);
+                assert_eq!(attributes[0].class_id, 2);
+                assert_eq!(attributes[0].value, AttributeValue::Boolean(true));
+            }
+            value => panic!("expected an update but got {:?}", value),
+        }
+    }
+
+    #[test]
+    fn get_replication_rejects_updates_to_destroyed_actors() {
+        let mut context = context(
+            &[
+                "TAGame.Car_Example_TA",
+                "Archetypes.Car.Example",
+                "Engine.Actor:bHidden",
+            ],
+            &[0],
+            &[(0, 1, 2)],
+        );
+        let actor = bits_u32c(5, 1023);
+        let create = format!("{}110{}{}", actor, bits(0, 32), bits(1, 32));
+        let destroy = format!("{}0", actor);
+        let update = format!("{}101{}10", actor, bits_u32c(1, 1));
+        let mut bit_get = bit_get(&format!("{}{}{}", create, destroy, update));
+
+        bit_get.get_replication(&mut context).unwrap();
+        bit_get.get_replication(&mut context).unwrap();
+        match bit_get.get_replication(&mut context) {
+            Err(BitGetError::Located { problem, .. }) => match *problem {
+                BitGetError::UnknownActor(5) => (),
+                problem => panic!("expected an unknown actor but got {:?}", problem),
+            },
+            result => panic!("expected an error but got {:?}", result.map(|_| ())),
+        }
+    }
+
+    fn vector_bits(size: u32, limit: u32, vector: (i32, i32, i32)) -> String {
+        let bias = u32_i32(1 << (size + 1));
+        let component = |x: i32| bits((x + bias) as u32, size + 2);
+        format!(
+            "{}{}{}{}",
+            bits_u32c(size, limit),
+            component(vector.0),
+            component(vector.1),
+            component(vector.2)
+        )
+    }
+
+    #[test]
+    fn get_vector_reads_small_sizes_before_net_version_7() {
+        let mut context = context(&[], &[], &[]);
+        context.version = (868, 20, 0);
+        let stream = vector_bits(17, 19, (-12_345, 0, 67_890));
+        let mut reader = bit_get(&stream);
+        let vector = reader.get_vector(&context).unwrap();
+        assert_eq!(
+            vector,
+            Vector {
+                x: -12_345,
+                y: 0,
+                z: 67_890
+            }
+        );
+        assert_eq!(reader.get_bit_index(), stream.len());
+    }
+
+    #[test]
+    fn get_vector_reads_large_sizes_since_net_version_7() {
+        let context = context(&[], &[], &[]);
+        let stream = vector_bits(20, 21, (-1_000_000, 5, 2_000_000));
+        let mut reader = bit_get(&stream);
+        let vector = reader.get_vector(&context).unwrap();
+        assert_eq!(
+            vector,
+            Vector {
+                x: -1_000_000,
+                y: 5,
+                z: 2_000_000
+            }
+        );
+        assert_eq!(reader.get_bit_index(), stream.len());
+
+        let mut old_context = context;
+        old_context.version = (868, 22, 6);
+        let mut reader = bit_get(&stream);
+        let vector = reader.get_vector(&old_context).unwrap();
+        assert_ne!(
+            vector,
+            Vector {
+                x: -1_000_000,
+                y: 5,
+                z: 2_000_000
+            }
+        );
+    }
+
+    fn get_u8_bit_by_bit(bit_get: &mut BitGet) -> BitGetResult<u8> {
+        let mut byte = 0;
+        for index in 0..8 {
+            if bit_get.get_bool()? {
+                byte |= 1 << index;
+            }
+        }
+        Ok(byte)
+    }
+
+    #[test]
+    fn get_u8_matches_bit_by_bit_reads() {
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for _ in 0..1_000 {
+            let len = random() % 16;
+            let bytes: Vec<u8> = (0..len).map(|_| random() as u8).collect();
+            let skip = random() % 8;
+            let mut fast = BitGet::new(bytes.clone());
+            let mut slow = BitGet::new(bytes.clone());
+            fast.get_bits(skip as u32).ok();
+            slow.get_bits(skip as u32).ok();
+            for _ in 0..len + 1 {
+                let expected = get_u8_bit_by_bit(&mut slow);
+                match (fast.get_u8(), expected) {
+                    (Ok(actual), Ok(expected)) => assert_eq!(actual, expected, "{:?}", bytes),
+                    (
+                        Err(BitGetError::IndexOutOfBounds { index: actual, .. }),
+                        Err(BitGetError::IndexOutOfBounds {
+                            index: expected, ..
+                        }),
+                    ) => {
+                        assert_eq!(actual, expected, "{:?}", bytes);
+                        break;
+                    }
+                    (actual, expected) => panic!("{:?} but expected {:?}", actual, expected),
+                }
+            }
+            let mut fast = BitGet::new(bytes.clone());
+            let mut slow = BitGet::new(bytes.clone());
+            fast.get_bits(skip as u32).ok();
+            slow.get_bits(skip as u32).ok();
+            let count = u64_usize(len / 2).unwrap();
+            let expected: Vec<u8> = (0..count)
+                .map(|_| get_u8_bit_by_bit(&mut slow).unwrap())
+                .collect();
+            assert_eq!(fast.get_vec(count).unwrap(), expected, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn frame_decoder_resumes_where_it_stopped() {
+        let new_context = || {
+            let mut context = context(
+                &[
+                    "TAGame.Car_Example_TA",
+                    "Archetypes.Car.Example",
+                    "Engine.Actor:bHidden",
+                ],
+                &[0],
+                &[(0, 1, 2)],
+            );
+            context.num_frames = 4;
+            context
+        };
+        let actor = bits_u32c(5, 1023);
+        let frame = |time: f32, replications: &str| {
+            format!(
+                "{}{}{}0",
+                bits(time.to_bits(), 32),
+                bits(0, 32),
+                replications
+            )
+        };
+        let create = format!("1{}110{}{}", actor, bits(0, 32), bits(1, 32));
+        let update = |hidden| format!("1{}101{}{}0", actor, bits_u32c(1, 1), hidden);
+        let stream = format!(
+            "{}{}{}{}",
+            frame(0.0, &create),
+            frame(0.5, &update("1")),
+            frame(1.0, &update("0")),
+            frame(1.5, "")
+        );
+        let bytes = bit_get(&stream).bytes;
+
+        let mut whole = FrameDecoder::new(bytes.clone(), new_context()).unwrap();
+        let expected = whole.decode(4).unwrap();
+        assert_eq!(expected.len(), 4);
+
+        let mut halves = FrameDecoder::new(bytes, new_context()).unwrap();
+        let mut actual = halves.decode(2).unwrap();
+        assert_eq!(halves.frames_decoded(), 2);
+        assert_eq!(halves.remaining(), 2);
+        actual.extend(halves.decode(2).unwrap());
+        assert!(halves.is_done());
+        assert_eq!(actual, expected);
+    }
+
+    fn text_bytes(size: i32, bytes: &[u8]) -> Vec<u8> {
+        let mut result = size.to_le_bytes().to_vec();
+        result.extend_from_slice(bytes);
+        result
+    }
+
+    #[test]
+    fn get_text_rejects_sizes_larger_than_the_input() {
+        let mut get = Get::new(text_bytes(1_000, b"abc\0"));
+        match get.get_text() {
+            Err(GetError::TextTooLarge { size: 1_000 }) => (),
+            result => panic!("expected text too large but got {:?}", result),
+        }
+        let mut get = Get::new(text_bytes(-1_000, b"a\0b\0\0\0"));
+        match get.get_text() {
+            Err(GetError::TextTooLarge { size: -1_000 }) => (),
+            result => panic!("expected text too large but got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn get_text_keeps_the_eight_byte_magic_size() {
+        let mut get = Get::new(text_bytes(0x0500_0000, b"abcdefg\0"));
+        let text = get.get_text().unwrap();
+        assert_eq!(text.as_str(), "abcdefg");
+        assert_eq!(get.index, 12);
+    }
+
+    #[test]
+    fn utf_16_reports_odd_lengths() {
+        match utf_16(&[0x41, 0x00, 0x42]) {
+            Err(Utf16Error::OddLength) => (),
+            _ => panic!("expected an odd length"),
+        }
+    }
+
+    #[test]
+    fn get_text_reports_invalid_utf_16_units() {
+        let mut get = Get::new(text_bytes(-2, &[0x00, 0xd8, 0x00, 0x00]));
+        match get.get_text() {
+            Err(GetError::Utf16InvalidUnits(bytes)) => {
+                assert_eq!(bytes, vec![0x00, 0xd8, 0x00, 0x00])
+            }
+            result => panic!("expected invalid units but got {:?}", result),
+        }
+        let mut get = Get::new(text_bytes(-2, &[0x41, 0x00, 0x00, 0x00]));
+        assert_eq!(get.get_text().unwrap().as_str(), "A");
+    }
+
+    fn name_bytes(name: &str) -> Vec<u8> {
+        let name = format!("{}\0", name);
+        text_bytes(usize_u32(name.len()).map_or(0, u32_i32), name.as_bytes())
+    }
+
+    fn unknown_property_bytes() -> Vec<u8> {
+        let mut bytes = name_bytes("Mystery");
+        bytes.extend(name_bytes("MysteryProperty"));
+        bytes.extend(3_u64.to_le_bytes().iter());
+        bytes.extend([1, 2, 3].iter());
+        bytes.extend(name_bytes("None"));
+        bytes
+    }
+
+    fn section_bytes(body: &[u8]) -> Vec<u8> {
+        let mut bytes = usize_u32(body.len()).unwrap().to_le_bytes().to_vec();
+        bytes.extend(crc_32(body).to_le_bytes().iter());
+        bytes.extend(body);
+        bytes
+    }
+
+    // A replay with one int property and no network data.
+    fn replay_bytes(team_size: u32) -> Vec<u8> {
+        let mut header = Vec::new();
+        for x in &[868_u32, 22, 7] {
+            header.extend(x.to_le_bytes().iter());
+        }
+        header.extend(name_bytes("TAGame.Replay_Soccar_TA"));
+        header.extend(name_bytes("TeamSize"));
+        header.extend(name_bytes("IntProperty"));
+        header.extend(4_u64.to_le_bytes().iter());
+        header.extend(team_size.to_le_bytes().iter());
+        header.extend(name_bytes("None"));
+        let mut bytes = section_bytes(&header);
+        bytes.extend(section_bytes(&[0; 40]));
+        bytes
+    }
+
+    #[test]
+    fn get_property_rejects_unknown_types_by_default() {
+        let mut get = Get::new(unknown_property_bytes());
+        match get.get_dictionary(|this| this.get_property()) {
+            Err(GetError::UnknownProperty(label)) => assert_eq!(label, "MysteryProperty\0"),
+            result => panic!("expected an unknown property but got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn get_property_skips_unknown_types_when_lenient() {
+        let bytes = unknown_property_bytes();
+        let len = bytes.len();
+        let mut get = Get::new(bytes);
+        get.set_lenient(true);
+        let properties = get.get_dictionary(|this| this.get_property()).unwrap();
+        let property = properties.get("Mystery").unwrap();
+        assert_eq!(
+            property.value(),
+            &PropertyValue::Unknown {
+                label: String::from("MysteryProperty\0"),
+                bytes: vec![1, 2, 3],
+            }
+        );
+        assert_eq!(properties.last(), "None");
+        assert_eq!(get.index, len);
+    }
+
+    #[test]
+    fn get_text_rejects_sizes_larger_than_the_stream() {
+        let stream = format!("1{}{}", bits(0x7fff_ffff, 32), bits(0, 16));
+        let mut reader = bit_get(&stream);
+        reader.get_bool().unwrap();
+        match reader.get_text() {
+            Err(BitGetError::SizeOutOfRange(0x7fff_ffff)) => (),
+            result => panic!("expected size out of range but got {:?}", result),
+        }
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn parse_detects_gzip() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let bytes = text_bytes(1_000, b"abc\0");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&bytes).unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+
+        let expected = parse(&bytes).unwrap_err().to_string();
+        assert_eq!(parse(&compressed).unwrap_err().to_string(), expected);
+
+        match parse(&compressed[..compressed.len() / 2]) {
+            Err(GetError::Io(_)) => {}
+            x => panic!("unexpected {:?}", x),
+        }
+    }
+
+    #[test]
+    fn get_attribute_value_reads_roles_as_eleven_bit_enums() {
+        for name in &["Engine.Actor:RemoteRole\0", "Engine.Actor:Role\0"] {
+            assert_eq!(get_enum_width(name), Some(11));
+            let mut reader = bit_get(&format!("{}1", bits(3, 11)));
+            match reader.get_attribute_value_role(name) {
+                Ok(AttributeValue::Role(NetworkRole::Authority)) => {}
+                x => panic!("unexpected {:?}", x),
+            }
+            assert_eq!(reader.get_bits(1).ok(), Some(1));
+        }
+    }
+
+    #[test]
+    fn playlist_is_unknown_unless_the_header_says_otherwise() {
+        assert_eq!(header(vec![]).playlist(), Playlist::Unknown(None));
+        assert_eq!(
+            header(vec![("TeamSize", PropertyValue::Int(3))]).playlist(),
+            Playlist::Unknown(None)
+        );
+        assert_eq!(
+            header(vec![(
+                "MapName",
+                PropertyValue::Name(text("HoopsStadium_P"))
+            )])
+            .playlist(),
+            Playlist::Hoops
+        );
+        assert_eq!(
+            header(vec![("MatchType", PropertyValue::Name(text("Private")))]).playlist(),
+            Playlist::Private
+        );
+        assert_eq!(Playlist::from_id(3), Playlist::Standard);
+        assert_eq!(Playlist::from_id(99), Playlist::Unknown(Some(99)));
+    }
+
+    #[test]
+    fn frame_decoder_keeps_the_position_of_a_frame_count_mismatch() {
+        let new_context = || {
+            let mut context = context(&[], &[], &[]);
+            context.num_frames = 2;
+            context
+        };
+        let frame = format!("{}{}0", bits(0, 32), bits(0, 32));
+
+        let bytes = bit_get(&frame).bytes;
+        let mut decoder = FrameDecoder::new(bytes, new_context()).unwrap();
+        match decoder.decode(2) {
+            Err(BitGetError::Located { position, problem }) => {
+                assert_eq!(position.frame, 1);
+                match *problem {
+                    BitGetError::FrameCountMismatch {
+                        expected: 2,
+                        actual: 1,
+                    } => {}
+                    x => panic!("unexpected {:?}", x),
+                }
+            }
+            x => panic!("unexpected {:?}", x),
+        }
+
+        let truncated = format!("{}{}{}1", frame, bits(0, 32), bits(0, 32));
+        let bytes = bit_get(&truncated).bytes;
+        let mut decoder = FrameDecoder::new(bytes, new_context()).unwrap();
+        match decoder.decode(2) {
+            Err(BitGetError::Located { position, problem }) => {
+                assert_eq!(position.frame, 1);
+                match *problem {
+                    BitGetError::IndexOutOfBounds { .. } => {}
+                    x => panic!("unexpected {:?}", x),
+                }
+            }
+            x => panic!("unexpected {:?}", x),
+        }
+    }
+
+    #[test]
+    fn bit_reader_rejects_more_than_32_bits() {
+        let mut bit_get = bit_get(&bits(0, 32));
+        let mut reader = BitReader {
+            bit_get: &mut bit_get,
+        };
+        assert_eq!(reader.peek_bits(32).ok(), Some(0));
+        match reader.peek_bits(33) {
+            Err(BitGetError::TooManyBits(33)) => {}
+            x => panic!("unexpected {:?}", x),
+        }
+        match reader.get_bits(64) {
+            Err(BitGetError::TooManyBits(64)) => {}
+            x => panic!("unexpected {:?}", x),
+        }
+        assert_eq!(reader.get_bits(32).ok(), Some(0));
+    }
+
+    #[test]
+    fn stat_events_are_attributed_to_the_replicating_player() {
+        let event = || AttributeValue::StatEvent {
+            unknown: false,
+            object_id: 0,
+        };
+        let frame = |replications| Frame {
+            time: 1.0,
+            delta: 0.0,
+            replications,
+        };
+        let content = content(
+            &["StatEvents.Events.EpicSave"],
+            vec![
+                frame(vec![
+                    replication(1, created("TAGame.PRI_TA")),
+                    replication(2, created("TAGame.GameEvent_Soccar_TA")),
+                ]),
+                frame(vec![
+                    replication(1, updated("TAGame.PRI_TA:ReplicatedStatEvent", event())),
+                    replication(
+                        2,
+                        updated("TAGame.GameEvent_Soccar_TA:ReplicatedStatEvent", event()),
+                    ),
+                ]),
+                frame(vec![
+                    replication(1, ReplicationValue::Destroyed),
+                    replication(1, created("TAGame.Car_TA")),
+                    replication(1, updated("TAGame.Car_TA:Unknown", event())),
+                ]),
+            ],
+        );
+        let players: Vec<_> = content
+            .stat_events()
+            .into_iter()
+            .map(|event| (event.frame, event.player, event.event))
+            .collect();
+        let epic_save = || String::from("StatEvents.Events.EpicSave");
+        assert_eq!(
+            players,
+            vec![
+                (1, Some(1), epic_save()),
+                (1, None, epic_save()),
+                (2, None, epic_save())
+            ]
+        );
+    }
+
+    #[test]
+    fn game_times_measure_drift_from_frame_times() {
+        let context = context(&[], &[], &[]);
+        let name = "TAGame.GameEvent_Soccar_TA:SecondsRemaining";
+        let seconds = |x| {
+            let mut reader = bit_get(&bits(x, 32));
+            match reader.get_attribute_value(&context, &format!("{}\0", name)) {
+                Ok(value) => value,
+                x => panic!("unexpected {:?}", x),
+            }
+        };
+        let frame = |time, x| Frame {
+            time,
+            delta: 0.0,
+            replications: vec![replication(1, updated(name, seconds(x)))],
+        };
+        let game_length = Frame {
+            time: 0.0,
+            delta: 0.0,
+            replications: vec![replication(
+                1,
+                updated(
+                    "TAGame.GameEvent_Soccar_TA:GameTime",
+                    AttributeValue::Int(300),
+                ),
+            )],
+        };
+        let content = content(&[], vec![game_length, frame(1.0, 300), frame(3.5, 298)]);
+        let times = content.game_times();
+        assert_eq!(
+            times
+                .iter()
+                .map(|x| (x.frame, x.seconds_remaining))
+                .collect::<Vec<_>>(),
+            vec![(1, 300), (2, 298)]
+        );
+        assert_eq!(times[1].drift_since(&times[0]), 0.5);
+        assert_eq!(times[0].drift_since(&times[0]), 0.0);
+
+        match bit_get(&bits(0, 32)).get_attribute_value(&context, "Engine.WorldInfo:WorldTime\0") {
+            Err(BitGetError::UnknownAttribute(_)) => {}
+            x => panic!("unexpected {:?}", x),
+        }
+    }
+
+    #[test]
+    fn features_report_active_mutators() {
+        let replay = |replications| Replay {
+            header: Section {
+                size: 0,
+                crc: 0,
+                value: header(vec![]),
+            },
+            content: Section {
+                size: 0,
+                crc: 0,
+                value: content(
+                    &[],
+                    vec![Frame {
+                        time: 0.0,
+                        delta: 0.0,
+                        replications,
+                    }],
+                ),
+            },
+            debug: None,
+        };
+        let gravity = |x| {
+            replication(
+                1,
+                updated("Engine.WorldInfo:WorldGravityZ", AttributeValue::Float(x)),
+            )
+        };
+        assert!(!replay(vec![]).features().has_mutators);
+        assert!(!replay(vec![gravity(-650.)]).features().has_mutators);
+        assert!(replay(vec![gravity(-325.)]).features().has_mutators);
+    }
+
+    #[test]
+    fn validate_keyframes_skips_frames_without_a_frame_count() {
+        let mut content = content(&[], Vec::new());
+        content.size = 2;
+        content.keyframes = list(vec![Keyframe {
+            time: 1.0,
+            frame: 30,
+            offset: 15,
+        }]);
+        assert!(content.validate_keyframes(None).is_ok());
+        assert!(content.validate_keyframes(Some(31)).is_ok());
+        match content.validate_keyframes(Some(30)) {
+            Err(GetError::KeyframeOutOfRange {
+                frame: 30,
+                offset: 15,
+            }) => {}
+            x => panic!("unexpected {:?}", x),
+        }
+        content.size = 1;
+        assert!(content.validate_keyframes(None).is_err());
+    }
+
+    #[test]
+    fn get_debug_ignores_trailing_bytes_that_are_not_a_dictionary() {
+        let bytes = text_bytes(5, b"None\0");
+        let mut get = Get::from_slice(&bytes);
+        assert_eq!(get.get_debug().map(|debug| debug.value.len()), Some(0));
+
+        let bytes = [0xff, 0xff, 0xff, 0xff, 0x01];
+        let mut get = Get::from_slice(&bytes);
+        assert!(get.get_debug().is_none());
+        assert_eq!(get.index, 0);
+    }
+
+    #[test]
+    fn name_attributes_trims_nul_terminators() {
+        let content = content(&["Engine.Actor:bHidden", "TAGame.Ball_TA"], Vec::new());
+        let mut attributes = BTreeMap::new();
+        attributes.insert(1, 0);
+        attributes.insert(2, 5);
+        let mut expected = BTreeMap::new();
+        expected.insert(1, String::from("Engine.Actor:bHidden"));
+        assert_eq!(content.name_attributes(&attributes), expected);
+    }
+
+    #[test]
+    fn get_list_does_not_trust_the_size_for_allocation() {
+        let bytes = [0xff, 0xff, 0xff, 0x0f, 0x01, 0x02];
+        let mut get = Get::from_slice(&bytes);
+        match get.get_list(Get::get_u8) {
+            Err(GetError::IndexOutOfBounds { .. }) => {}
+            x => panic!("unexpected {:?}", x),
+        }
+
+        let bytes = [0x02, 0x00, 0x00, 0x00, 0x01, 0x02];
+        let mut get = Get::from_slice(&bytes);
+        assert_eq!(
+            get.get_list(Get::get_u8).ok().map(|list| list.value),
+            Some(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn get_u32c_reads_the_largest_limit() {
+        let mut reader = bit_get(&"1".repeat(32));
+        assert_eq!(reader.get_u32c(u32::MAX).unwrap().value, u32::MAX);
+        assert_eq!(reader.get_bit_index(), 32);
+
+        let mut reader = bit_get(&bits(0, 32));
+        assert_eq!(reader.get_u32c(u32::MAX).unwrap().value, 0);
+        assert_eq!(reader.get_bit_index(), 32);
+
+        let mut reader = bit_get(&bits(1 << 31, 32));
+        assert_eq!(reader.get_u32c(1 << 31).unwrap().value, 1 << 31);
+        assert_eq!(reader.get_bit_index(), 32);
+    }
+
+    #[cfg(feature = "analysis")]
+    #[test]
+    fn touches_find_the_car_that_changed_the_ball_velocity() {
+        let vector = |x, y, z| Vector { x, y, z };
+        let rigid_body = |location, velocity| AttributeValue::RigidBody {
+            sleeping: false,
+            location,
+            rotation: Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                w: 1.0,
+            },
+            linear_velocity: Some(velocity),
+            angular_velocity: Some(vector(0, 0, 0)),
+        };
+        let ball = |x, velocity| {
+            replication(
+                1,
+                updated(
+                    "TAGame.RBActor_TA:ReplicatedRBState",
+                    rigid_body(vector(x, 0, 9300), vector(velocity, 0, 0)),
+                ),
+            )
+        };
+        let car = |actor, x| {
+            replication(
+                actor,
+                updated(
+                    "TAGame.RBActor_TA:ReplicatedRBState",
+                    rigid_body(vector(x, 0, 1700), vector(0, 0, 0)),
+                ),
+            )
+        };
+        let frame = |replications| Frame {
+            time: 0.0,
+            delta: 0.0,
+            replications,
+        };
+        let content = content(
+            &[],
+            vec![
+                frame(vec![
+                    replication(1, created("TAGame.Ball_TA")),
+                    replication(2, created("TAGame.Car_TA")),
+                    replication(3, created("TAGame.Car_TA")),
+                ]),
+                frame(vec![ball(0, 0), car(2, -50_000), car(3, 90_000)]),
+                frame(vec![ball(0, 0), car(2, -20_000)]),
+                frame(vec![ball(100, 150_000), car(2, -15_000)]),
+                frame(vec![ball(1_600, 150_000)]),
+            ],
+        );
+        assert_eq!(
+            content.touches(25_000, 50_000),
+            vec![Touch {
+                frame: 3,
+                actor: 2,
+                position: vector(100, 0, 9300),
+            }]
+        );
+    }
+
+    #[test]
+    fn get_unique_id_reads_epic_ids_after_psy_net_ids() {
+        let mut context = context(&[], &[], &[]);
+        context.version = (868, 24, 10);
+        let psy_net: String = (1..=8).map(|x| bits(x, 8)).collect();
+        let epic: String = b"abc\0".iter().map(|&x| bits(u32::from(x), 8)).collect();
+        let stream = format!(
+            "{}{}{}{}{}",
+            bits(7, 8),
+            psy_net,
+            bits(4, 32),
+            epic,
+            bits(0, 8)
+        );
+
+        let mut reader = bit_get(&stream);
+        let unique_id = reader.get_unique_id(&context).unwrap();
+        assert_eq!(
+            unique_id.remote,
+            RemoteId::PsyNet(vec![1, 2, 3, 4, 5, 6, 7, 8])
+        );
+
+        context.version = (868, 32, 0);
+        let mut reader = bit_get(&stream);
+        let unique_id = reader.get_unique_id(&context).unwrap();
+        assert_eq!(unique_id.platform, Platform::PsyNet);
+        assert_eq!(
+            unique_id.remote,
+            RemoteId::PsyNetEpic {
+                psy_net: vec![1, 2, 3, 4, 5, 6, 7, 8],
+                epic: String::from("abc"),
+            }
+        );
+        assert_eq!(unique_id.local, 0);
+
+        let stream = format!("{}{}{}{}", bits(7, 8), psy_net, bits(0, 32), bits(0, 8));
+        let mut reader = bit_get(&stream);
+        let unique_id = reader.get_unique_id(&context).unwrap();
+        assert_eq!(
+            unique_id.remote,
+            RemoteId::PsyNet(vec![1, 2, 3, 4, 5, 6, 7, 8])
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_custom_demolish_effects() {
+        let context = context(&[], &[], &[]);
+        let stream = format!(
+            "1{}1{}0{}{}{}1",
+            bits(7, 32),
+            bits(3, 32),
+            bits(4, 32),
+            vector_bits(0, 21, (1, -1, 0)),
+            vector_bits(0, 21, (0, 0, 1))
+        );
+        let mut reader = bit_get(&stream);
+        let value = reader
+            .get_attribute_value(&context, "TAGame.Car_TA:ReplicatedDemolish_CustomFX\0")
+            .unwrap();
+        assert_eq!(
+            value,
+            AttributeValue::DemolishFx {
+                custom_demo_flag: true,
+                custom_demo_id: 7,
+                attacker: ActiveActor {
+                    active: true,
+                    actor: 3,
+                },
+                victim: ActiveActor {
+                    active: false,
+                    actor: 4,
+                },
+                attacker_velocity: Vector { x: 1, y: -1, z: 0 },
+                victim_velocity: Vector { x: 0, y: 0, z: 1 },
+            }
+        );
+        assert_eq!(reader.get_bits(1).ok(), Some(1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn header_serializes_text_without_nul_terminators() {
+        let header = header(vec![("MapName", PropertyValue::Name(text("Stadium_P")))]);
+        let json = serde_json::to_value(&header).unwrap();
+        assert_eq!(json["label"], "TAGame.Replay_Soccar_TA");
+        assert_eq!(json["properties"]["value"][0][0], "MapName");
+        assert_eq!(
+            json["properties"]["value"][0][1]["value"],
+            serde_json::json!({ "type": "Name", "value": "Stadium_P" })
+        );
+        assert_eq!(json["properties"]["last"], "None");
+        assert!(!json.to_string().contains("\\u0000"));
+    }
+
+    #[test]
+    fn dictionary_get_matches_keys_without_nul_terminators() {
+        let header = header(vec![("TeamSize", PropertyValue::Int(3))]);
+        let properties = header.properties();
+        assert_eq!(
+            properties.iter().next().map(|(key, _)| key),
+            Some("TeamSize")
+        );
+        assert_eq!(
+            properties.get("TeamSize").map(Property::value),
+            Some(&PropertyValue::Int(3))
+        );
+        assert_eq!(
+            properties.get("TeamSize\0").map(Property::value),
+            Some(&PropertyValue::Int(3))
+        );
+        assert!(properties.get("Team").is_none());
+        assert_eq!(header.property_int("TeamSize"), Some(3));
+    }
+
+    #[test]
+    fn frame_decoder_does_not_trust_the_frame_count_for_allocation() {
+        let mut context = context(&[], &[], &[]);
+        context.num_frames = context.options.max_frames;
+        let frame = format!("{}{}0", bits(0, 32), bits(0, 32));
+        let bytes = bit_get(&frame).bytes;
+        let mut decoder = FrameDecoder::new(bytes, context).unwrap();
+        match decoder.decode(usize::MAX) {
+            Err(BitGetError::Located { problem, .. }) => match *problem {
+                BitGetError::FrameCountMismatch { actual: 1, .. } => {}
+                x => panic!("unexpected {:?}", x),
+            },
+            x => panic!("unexpected {:?}", x),
+        }
+    }
+
+    #[test]
+    fn attribute_tables_include_inherited_attributes() {
+        let cache = |class, parent, index, objects: &[(u32, u32)]| Cache {
+            class,
+            parent,
+            index,
+            objects: list(
+                objects
+                    .iter()
+                    .map(|&(id, index)| Object { index, id })
+                    .collect(),
+            ),
+        };
+        let mut content = content(
+            &[
+                "Engine.Actor",
+                "Engine.Actor:Role",
+                "TAGame.Ball_TA",
+                "TAGame.Ball_TA:GameEvent",
+                "TAGame.Car_TA",
+                "TAGame.Car_TA:TeamPaint",
+            ],
+            Vec::new(),
+        );
+        content.caches = list(vec![
+            cache(0, 0, 1, &[(1, 1)]),
+            cache(2, 1, 2, &[(2, 3)]),
+            cache(4, 1, 3, &[(2, 5)]),
+        ]);
+        let table = |entries: &[(u32, &str)]| -> BTreeMap<u32, String> {
+            entries
+                .iter()
+                .map(|&(stream_id, name)| (stream_id, String::from(name)))
+                .collect()
+        };
+        let mut expected = BTreeMap::new();
+        expected.insert(0, table(&[(1, "Engine.Actor:Role")]));
+        expected.insert(
+            2,
+            table(&[(1, "Engine.Actor:Role"), (2, "TAGame.Ball_TA:GameEvent")]),
+        );
+        expected.insert(
+            4,
+            table(&[(1, "Engine.Actor:Role"), (2, "TAGame.Car_TA:TeamPaint")]),
+        );
+        assert_eq!(content.attribute_tables(), expected);
+    }
+
+    #[test]
+    fn get_text_counts_bytes_for_ascii_and_characters_for_utf_16() {
+        let bytes = text_bytes(0, b"");
+        let text = Get::new(bytes).get_text().unwrap();
+        assert_eq!((text.size(), text.as_str()), (0, ""));
+
+        let bytes = text_bytes(2, b"a\0");
+        let mut get = Get::new(bytes);
+        let text = get.get_text().unwrap();
+        assert_eq!((text.size(), text.as_str()), (2, "a"));
+        assert_eq!(get.index, 6);
+
+        let bytes = text_bytes(-2, &[0xe9, 0x00, 0x00, 0x00]);
+        let mut get = Get::new(bytes);
+        let text = get.get_text().unwrap();
+        assert_eq!((text.size(), text.as_str()), (-2, "\u{e9}"));
+        assert_eq!(get.index, 8);
+    }
+
+    #[test]
+    fn reset_parses_another_replay_with_the_same_get() {
+        let mut get = Get::new(replay_bytes(1));
+        let replay = get.get_replay().unwrap();
+        assert_eq!(replay.header().property_int("TeamSize"), Some(1));
+
+        let mut buffer = get.reset(Vec::new()).unwrap();
+        let capacity = buffer.capacity();
+        buffer.clear();
+        buffer.extend(replay_bytes(2));
+        assert_eq!(buffer.capacity(), capacity);
+        get.reset(buffer);
+        let replay = get.get_replay().unwrap();
+        assert_eq!(replay.header().property_int("TeamSize"), Some(2));
+
+        let bytes = replay_bytes(3);
+        let mut get = Get::from_slice(&bytes);
+        assert!(get.reset(replay_bytes(3)).is_none());
+        assert_eq!(
+            get.get_replay().unwrap().header().property_int("TeamSize"),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn frame_decoder_uses_registered_attribute_decoders() {
+        fn get_three_bits(reader: &mut BitReader) -> Result<AttributeValue, BitGetError> {
+            let x = reader.get_bits(3)?;
+            Ok(AttributeValue::Byte(u32_u8(x).unwrap()))
+        }
+
+        let mut get = Get::new(Vec::new());
+        get.set_attribute_decoder("TAGame.Custom_TA:Thing", get_three_bits);
+        let mut context = context(
+            &[
+                "TAGame.Custom_TA",
+                "Archetypes.Custom.Example",
+                "TAGame.Custom_TA:Thing",
+            ],
+            &[0],
+            &[(0, 1, 2)],
+        );
+        context.num_frames = 1;
+        context.options = get.options.clone();
+        let actor = bits_u32c(5, 1023);
+        let create = format!("1{}110{}{}", actor, bits(0, 32), bits(1, 32));
+        let update = format!("1{}101{}{}0", actor, bits_u32c(1, 1), bits(6, 3));
+        let stream = format!("{}{}{}{}0", bits(0, 32), bits(0, 32), create, update);
+        let mut decoder = FrameDecoder::new(bit_get(&stream).bytes, context).unwrap();
+        let frames = decoder.decode(1).unwrap();
+        match &frames[0].replications[1].value {
+            ReplicationValue::Updated(attributes) => {
+                assert_eq!(attributes[0].object, "TAGame.Custom_TA:Thing\0");
+                assert_eq!(attributes[0].value, AttributeValue::Byte(6));
+            }
+            x => panic!("unexpected {:?}", x),
+        }
+    }
+
+    #[test]
+    fn get_attribute_value_float_applies_the_float_policy_to_nan() {
+        let nan = 0x7fc0_0001;
+        let mut context = context(&[], &[], &[]);
+
+        context.options.float_policy = FloatPolicy::Allow;
+        match bit_get(&bits(nan, 32)).get_attribute_value_float(&context) {
+            Ok(AttributeValue::Float(x)) => assert_eq!(x.to_bits(), nan),
+            x => panic!("unexpected {:?}", x),
+        }
+
+        context.options.float_policy = FloatPolicy::Reject;
+        match bit_get(&bits(nan, 32)).get_attribute_value_float(&context) {
+            Err(BitGetError::InvalidFloat(x)) => assert_eq!(x, nan),
+            x => panic!("unexpected {:?}", x),
+        }
+
+        context.options.float_policy = FloatPolicy::Zero;
+        match bit_get(&bits(nan, 32)).get_attribute_value_float(&context) {
+            Ok(AttributeValue::Float(x)) => assert_eq!(x.to_bits(), 0),
+            x => panic!("unexpected {:?}", x),
+        }
+
+        // Ordinary values pass through every policy.
+        match bit_get(&bits(1.5_f32.to_bits(), 32)).get_attribute_value_float(&context) {
+            Ok(AttributeValue::Float(x)) => assert_eq!(x, 1.5),
+            x => panic!("unexpected {:?}", x),
+        }
+    }
+
+    #[test]
+    fn conversions_reject_values_out_of_range() {
+        assert_eq!(i32_usize(-1), None);
+        assert_eq!(u32_u8(256), None);
+        assert_eq!(u32_u8(255), Some(255));
+
+        let mut get = Get::new(text_bytes(i32::MIN, b""));
+        match get.get_text() {
+            Err(GetError::SizeOutOfRange(x)) => assert_eq!(x, i64::from(i32::MIN)),
+            x => panic!("unexpected {:?}", x),
+        }
+
+        let context = context(&["TAGame.Car_TA"], &[0], &[]);
+        let stream = format!("110{}{}", bits(0, 32), bits(u32::MAX, 32));
+        match bit_get(&stream).get_replication_value(&context, 1) {
+            Err(BitGetError::UnknownObject(x)) => assert_eq!(x, u32::MAX),
+            x => panic!("unexpected {:?}", x),
+        }
+    }
+
+    #[test]
+    fn frames_iterate_in_order_with_their_replications() {
+        let frame = |time, replications| Frame {
+            time,
+            delta: 0.0,
+            replications,
+        };
+        let content = content(
+            &[],
+            vec![
+                frame(0.0, vec![replication(1, created("TAGame.Ball_TA"))]),
+                frame(0.5, Vec::new()),
+                frame(
+                    1.0,
+                    vec![
+                        replication(1, ReplicationValue::Destroyed),
+                        replication(2, created("TAGame.Ball_TA")),
+                    ],
+                ),
+            ],
+        );
+        let counts: Vec<_> = content
+            .frames()
+            .map(|frame| (frame.time(), frame.replications().len()))
+            .collect();
+        assert_eq!(counts, vec![(0.0, 1), (0.5, 0), (1.0, 2)]);
+        assert_eq!(
+            content.frames().next().unwrap().replications()[0].actor(),
+            1
+        );
+    }
+
+    #[test]
+    fn get_replication_reads_spawn_data_for_new_ball_and_scoreboard_classes() {
+        let mut context = context(
+            &[
+                "TAGame.Ball_Haunted_TA",
+                "Archetypes.Ball.Ball_Haunted",
+                "TAGame.InMapScoreboard_TA",
+                "Archetypes.InMapScoreboard",
+            ],
+            &[0, 2],
+            &[],
+        );
+        let stream = format!(
+            "{}110{}{}{}1{}00",
+            bits_u32c(5, 1023),
+            bits(0, 32),
+            bits(1, 32),
+            vector_bits(2, 21, (1, 2, 3)),
+            bits(64, 8)
+        );
+        let mut reader = bit_get(&stream);
+        let replication = reader.get_replication(&mut context).unwrap();
+        match replication.value {
+            ReplicationValue::Created {
+                ref class,
+                ref location,
+                ref rotation,
+                ..
+            } => {
+                assert_eq!(class, "TAGame.Ball_Haunted_TA\0");
+                assert_eq!(location, &Some(Vector { x: 1, y: 2, z: 3 }));
+                assert_eq!(
+                    rotation,
+                    &Some(Rotation {
+                        x: Some(64),
+                        y: None,
+                        z: None,
+                    })
+                );
+            }
+            ref value => panic!("expected a created value but got {:?}", value),
+        }
+        assert_eq!(reader.get_bit_index(), stream.len());
+        assert_eq!(context.actors.get(&5), Some(&0));
+
+        // The scoreboard spawns with a location but no rotation.
+        let stream = format!(
+            "{}110{}{}{}",
+            bits_u32c(6, 1023),
+            bits(0, 32),
+            bits(3, 32),
+            vector_bits(0, 21, (0, 0, -1))
+        );
+        let mut reader = bit_get(&stream);
+        let replication = reader.get_replication(&mut context).unwrap();
+        match replication.value {
+            ReplicationValue::Created {
+                ref class,
+                ref location,
+                ref rotation,
+                ..
+            } => {
+                assert_eq!(class, "TAGame.InMapScoreboard_TA\0");
+                assert_eq!(location, &Some(Vector { x: 0, y: 0, z: -1 }));
+                assert_eq!(rotation, &None);
+            }
+            ref value => panic!("expected a created value but got {:?}", value),
         }
+        assert_eq!(reader.get_bit_index(), stream.len());
+        assert_eq!(context.actors.get(&6), Some(&2));
     }
-    match String::from_utf16(&units) {
-        Ok(string) => Some(string),
-        Err(_) => None,
+
+    #[test]
+    fn get_property_value_byte_reads_no_value_for_any_online_platform() {
+        for platform in &["OnlinePlatform_PS4", "OnlinePlatform_Dingo"] {
+            let mut bytes = name_bytes(platform);
+            bytes.extend(name_bytes("None"));
+            let mut get = Get::new(bytes);
+            match get.get_property_value_byte().unwrap() {
+                PropertyValue::Byte { key, value: None } => {
+                    assert_eq!(key.value, format!("{}\0", platform))
+                }
+                value => panic!("expected a platform byte but got {:?}", value),
+            }
+            assert_eq!(get.get_text().unwrap().value, "None\0");
+        }
+
+        let mut bytes = name_bytes("EGameState");
+        bytes.extend(name_bytes("Active"));
+        let mut get = Get::new(bytes);
+        match get.get_property_value_byte().unwrap() {
+            PropertyValue::Byte {
+                key,
+                value: Some(value),
+            } => {
+                assert_eq!(key.value, "EGameState\0");
+                assert_eq!(value.value, "Active\0");
+            }
+            value => panic!("expected an enum byte but got {:?}", value),
+        }
+    }
+
+    #[test]
+    fn crc_32_streams_chunks_to_the_same_checksum() {
+        let bytes: Vec<u8> = (0..768).map(|i| (i % 256) as u8).collect();
+        // Values from a bitwise implementation of the same polynomial.
+        assert_eq!(crc_32(b""), 0xefcb_f201);
+        assert_eq!(crc_32(b"123456789"), 0xdbfa_7673);
+        assert_eq!(crc_32(&bytes), 0xf17b_2287);
+        for size in &[1, 7, 256, 1_000] {
+            let mut crc = Crc32::new();
+            for chunk in bytes.chunks(*size) {
+                crc.update(chunk);
+            }
+            crc.update(&[]);
+            assert_eq!(crc.finalize(), crc_32(&bytes), "chunk size {}", size);
+        }
+    }
+
+    #[test]
+    fn get_section_rejects_a_declared_size_of_zero() {
+        let mut bytes = section_bytes(&[]);
+        bytes.extend(7_u32.to_le_bytes().iter());
+        let mut get = Get::new(bytes);
+        match get.get_section(Get::get_u32) {
+            Err(GetError::EmptySection) => (),
+            result => panic!("expected an empty section but got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn header_goals_reads_each_goal_in_order() {
+        let property = |value| Property {
+            label: text("IntProperty"),
+            size: 4,
+            value,
+        };
+        let goal = |frame, player: &str, team| Dictionary {
+            value: vec![
+                (text("frame"), property(PropertyValue::Int(frame))),
+                (
+                    text("PlayerName"),
+                    property(PropertyValue::Str(text(player))),
+                ),
+                (text("PlayerTeam"), property(PropertyValue::Int(team))),
+            ],
+            last: text("None"),
+        };
+        let with_goals = header(vec![(
+            "Goals",
+            PropertyValue::Array(list(vec![goal(120, "Alpha", 0), goal(4_567, "Bravo", 1)])),
+        )]);
+        assert_eq!(
+            with_goals.goals(),
+            vec![
+                Goal {
+                    frame: 120,
+                    player: String::from("Alpha"),
+                    team: 0,
+                },
+                Goal {
+                    frame: 4_567,
+                    player: String::from("Bravo"),
+                    team: 1,
+                },
+            ]
+        );
+        assert_eq!(header(Vec::new()).goals(), Vec::new());
+    }
+
+    #[test]
+    fn get_section_skips_padding_after_the_value() {
+        let mut body = 7_u32.to_le_bytes().to_vec();
+        body.extend([0, 0, 0, 0, 0].iter());
+        let mut bytes = section_bytes(&body);
+        bytes.extend(9_u32.to_le_bytes().iter());
+        let mut get = Get::new(bytes);
+        let section = get.get_section(Get::get_u32).unwrap();
+        assert_eq!(section.size, 9);
+        assert_eq!(section.value, 7);
+        assert_eq!(get.get_u32().unwrap(), 9);
+    }
+
+    // Decodes one attribute with the default context and checks that the
+    // whole stream was read.
+    fn attribute_value(name: &str, stream: &str) -> AttributeValue {
+        let context = context(&[], &[], &[]);
+        let mut reader = bit_get(stream);
+        let value = reader
+            .get_attribute_value(&context, &format!("{}\0", name))
+            .unwrap();
+        assert_eq!(reader.get_bit_index(), stream.len(), "{}", name);
+        value
+    }
+
+    #[test]
+    fn get_attribute_value_reads_bot_attributes() {
+        assert_eq!(
+            attribute_value("Engine.PlayerReplicationInfo:bBot", "1"),
+            AttributeValue::Boolean(true)
+        );
+        assert_eq!(
+            attribute_value("TAGame.PRI_TA:BotProductName", &bits(42, 32)),
+            AttributeValue::Int(42)
+        );
+        assert_eq!(
+            attribute_value("TAGame.PRI_TA:BotBannerProductID", &bits(0xffff_ffff, 32)),
+            AttributeValue::Int(-1)
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_crowd_attributes() {
+        assert_eq!(
+            attribute_value(
+                "TAGame.CrowdManager_TA:ReplicatedGlobalOneShotSound",
+                &format!("1{}", bits(17, 32))
+            ),
+            AttributeValue::FlaggedInt {
+                flag: true,
+                int: 17
+            }
+        );
+        assert_eq!(
+            attribute_value(
+                "TAGame.CrowdActor_TA:ModifiedNoise",
+                &bits(0.75_f32.to_bits(), 32)
+            ),
+            AttributeValue::Float(0.75)
+        );
+        assert_eq!(
+            attribute_value(
+                "TAGame.CrowdActor_TA:ReplicatedCountDownNumber",
+                &bits(3, 32)
+            ),
+            AttributeValue::Int(3)
+        );
+    }
+
+    // A replay with a frame count in the header and the given network stream.
+    fn network_replay_bytes(num_frames: u32, stream: &[u8]) -> Vec<u8> {
+        let mut header = Vec::new();
+        for x in &[868_u32, 22, 7] {
+            header.extend(x.to_le_bytes().iter());
+        }
+        header.extend(name_bytes("TAGame.Replay_Soccar_TA"));
+        header.extend(name_bytes("NumFrames"));
+        header.extend(name_bytes("IntProperty"));
+        header.extend(4_u64.to_le_bytes().iter());
+        header.extend(num_frames.to_le_bytes().iter());
+        header.extend(name_bytes("None"));
+        let mut body = vec![0; 8];
+        body.extend(usize_u32(stream.len()).unwrap().to_le_bytes().iter());
+        body.extend(stream);
+        body.extend([0; 28].iter());
+        let mut bytes = section_bytes(&header);
+        bytes.extend(section_bytes(&body));
+        bytes
+    }
+
+    #[test]
+    fn network_bits_consumed_stops_before_the_stream_padding() {
+        // One empty frame is two floats and a stop bit, padded to 10 bytes.
+        let replay = parse(&network_replay_bytes(1, &[0; 10])).unwrap();
+        assert_eq!(replay.content().frames().count(), 1);
+        assert_eq!(replay.content().network_bit_length(), 80);
+        assert_eq!(replay.content().network_bits_consumed(), 65);
+    }
+
+    #[test]
+    fn parse_wraps_network_stream_errors() {
+        match parse(&network_replay_bytes(1, &[0; 4])) {
+            Err(error) => {
+                assert!(error.source().is_some());
+                match error {
+                    GetError::BitGet(BitGetError::Located { problem, .. }) => match *problem {
+                        BitGetError::FrameCountMismatch {
+                            expected: 1,
+                            actual: 0,
+                        } => (),
+                        problem => panic!("expected a frame count mismatch but got {:?}", problem),
+                    },
+                    error => panic!("expected a located error but got {:?}", error),
+                }
+            }
+            result => panic!("expected a bit get error but got {:?}", result.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn get_attribute_value_reads_match_result_attributes() {
+        assert_eq!(
+            attribute_value("TAGame.GameEvent_Soccar_TA:bMatchEnded", "1"),
+            AttributeValue::Boolean(true)
+        );
+        for name in &[
+            "TAGame.GameEvent_Soccar_TA:GameWinner",
+            "TAGame.GameEvent_Soccar_TA:MatchWinner",
+        ] {
+            assert_eq!(
+                attribute_value(name, &format!("1{}", bits(9, 32))),
+                AttributeValue::FlaggedInt { flag: true, int: 9 }
+            );
+        }
+    }
+
+    #[test]
+    fn attribute_tables_match_the_names_decoded_from_stream_ids() {
+        let mut content = content(
+            &[
+                "TAGame.Ball_Example_TA",
+                "Archetypes.Ball.Example",
+                "Engine.Actor:bHidden",
+                "Engine.Actor:bBlockActors",
+            ],
+            Vec::new(),
+        );
+        content.names = list(vec![text("Ball")]);
+        content.classes = list(vec![Class {
+            name: text("TAGame.Ball_Example_TA"),
+            id: 0,
+        }]);
+        content.caches = list(vec![Cache {
+            class: 0,
+            parent: 0,
+            index: 1,
+            objects: list(vec![Object { index: 2, id: 1 }, Object { index: 3, id: 2 }]),
+        }]);
+        let mut context = Context::new(
+            &header(Vec::new()),
+            &content.names,
+            &content.objects,
+            &content.classes,
+            &content.caches,
+            Options::new(),
+        );
+        let actor = bits_u32c(5, 1023);
+        let stream = format!(
+            "{}110{}{}{}101{}10",
+            actor,
+            bits(0, 32),
+            bits(1, 32),
+            actor,
+            bits_u32c(2, 2)
+        );
+        let mut bit_get = bit_get(&stream);
+        bit_get.get_replication(&mut context).unwrap();
+        let replication = bit_get.get_replication(&mut context).unwrap();
+        let attribute = match replication.value {
+            ReplicationValue::Updated(ref attributes) => &attributes[0],
+            ref value => panic!("expected an update but got {:?}", value),
+        };
+        let tables = content.attribute_tables();
+        assert_eq!(
+            tables[&attribute.class_id()].get(&attribute.stream_id.value),
+            Some(&String::from("Engine.Actor:bBlockActors"))
+        );
+        assert_eq!(attribute.object, "Engine.Actor:bBlockActors\0");
+    }
+
+    #[test]
+    fn state_transitions_name_each_replicated_state() {
+        let frame = |time, replications| Frame {
+            time,
+            delta: 0.0,
+            replications,
+        };
+        let state = |index| {
+            replication(
+                1,
+                updated(
+                    "TAGame.GameEvent_TA:ReplicatedStateName",
+                    AttributeValue::Int(index),
+                ),
+            )
+        };
+        let mut content = content(
+            &[],
+            vec![
+                frame(0.0, vec![state(0)]),
+                frame(
+                    0.5,
+                    vec![replication(
+                        1,
+                        updated(
+                            "TAGame.GameEvent_TA:ReplicatedStateIndex",
+                            AttributeValue::Byte(1),
+                        ),
+                    )],
+                ),
+                frame(1.0, vec![state(1), state(9)]),
+            ],
+        );
+        content.names = list(vec![text("Countdown"), text("Active")]);
+        assert_eq!(
+            content.state_transitions(),
+            vec![
+                StateTransition {
+                    frame: 0,
+                    time: 0.0,
+                    name: String::from("Countdown"),
+                },
+                StateTransition {
+                    frame: 2,
+                    time: 1.0,
+                    name: String::from("Active"),
+                },
+            ]
+        );
+        assert_eq!(
+            attribute_value("TAGame.GameEvent_TA:ReplicatedStateIndex", &bits(4, 8)),
+            AttributeValue::Byte(4)
+        );
+    }
+
+    #[test]
+    fn keyframe_index_finds_the_last_keyframe_at_or_before() {
+        let mut content = content(&[], Vec::new());
+        content.keyframes = list(vec![
+            Keyframe {
+                time: 1.0,
+                frame: 10,
+                offset: 100,
+            },
+            Keyframe {
+                time: 2.0,
+                frame: 20,
+                offset: 200,
+            },
+            Keyframe {
+                time: 3.0,
+                frame: 30,
+                offset: 300,
+            },
+        ]);
+        let index = content.keyframe_index();
+        assert_eq!(index.at_time(0.5), None);
+        assert_eq!(index.at_time(2.0).map(|keyframe| keyframe.frame), Some(20));
+        assert_eq!(index.at_frame(29).map(|keyframe| keyframe.frame), Some(20));
+        assert_eq!(
+            index.at_frame(1_000).map(|keyframe| keyframe.frame),
+            Some(30)
+        );
+        assert_eq!(index.at_offset(99), None);
+        assert_eq!(
+            index.at_offset(100).map(|keyframe| keyframe.frame),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_rumble_attributes() {
+        assert_eq!(
+            attribute_value("TAGame.SpecialPickup_BallVelcro_TA:bHit", "1"),
+            AttributeValue::Boolean(true)
+        );
+        assert_eq!(
+            attribute_value("TAGame.CarComponent_TA:ReplicatedActive", &bits(31, 8)),
+            AttributeValue::Byte(31)
+        );
+        assert_eq!(
+            attribute_value(
+                "TAGame.SpecialPickup_Targeted_TA:Targeted",
+                &format!("1{}", bits(12, 32))
+            ),
+            AttributeValue::FlaggedInt {
+                flag: true,
+                int: 12
+            }
+        );
+        assert_eq!(
+            attribute_value(
+                "TAGame.SpecialPickup_BallVelcro_TA:BreakTime",
+                &bits(2.5_f32.to_bits(), 32)
+            ),
+            AttributeValue::Float(2.5)
+        );
+        assert_eq!(
+            attribute_value("TAGame.RumblePickups_TA:ConcurrentItemCount", &bits(2, 32)),
+            AttributeValue::Int(2)
+        );
+    }
+
+    #[test]
+    fn get_location_reads_the_largest_size_without_overflowing() {
+        let context = context(&[], &[], &[]);
+        let stream = vector_bits(21, 21, (-4_194_304, 0, 4_194_303));
+        let mut reader = bit_get(&stream);
+        assert_eq!(
+            reader.get_location(&context).unwrap(),
+            Vector {
+                x: -4_194_304,
+                y: 0,
+                z: 4_194_303
+            }
+        );
+        assert_eq!(reader.get_bit_index(), stream.len());
+    }
+
+    #[test]
+    fn frames_with_keyframes_marks_the_keyframe_frames() {
+        let frame = |time| Frame {
+            time,
+            delta: 0.5,
+            replications: Vec::new(),
+        };
+        let keyframe = |frame| Keyframe {
+            time: 0.0,
+            frame,
+            offset: 0,
+        };
+        let mut content = content(&[], vec![frame(0.0), frame(0.5), frame(1.0), frame(1.5)]);
+        content.keyframes = list(vec![keyframe(0), keyframe(2)]);
+        let marks: Vec<_> = content
+            .frames_with_keyframes()
+            .map(|(frame, is_keyframe)| (frame.time(), is_keyframe))
+            .collect();
+        assert_eq!(
+            marks,
+            vec![(0.0, true), (0.5, false), (1.0, true), (1.5, false)]
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_camera_flags() {
+        for name in &[
+            "TAGame.CameraSettingsActor_TA:bUsingSwivel",
+            "TAGame.PRI_TA:bUsingFreecam",
+        ] {
+            assert_eq!(attribute_value(name, "1"), AttributeValue::Boolean(true));
+            assert_eq!(attribute_value(name, "0"), AttributeValue::Boolean(false));
+        }
+    }
+
+    #[test]
+    fn was_forfeit_needs_a_true_forfeit_flag() {
+        let replay = |replications| Replay {
+            header: Section {
+                size: 0,
+                crc: 0,
+                value: header(vec![]),
+            },
+            content: Section {
+                size: 0,
+                crc: 0,
+                value: content(
+                    &[],
+                    vec![Frame {
+                        time: 0.0,
+                        delta: 0.0,
+                        replications,
+                    }],
+                ),
+            },
+            debug: None,
+        };
+        let flag = |name, x| replication(1, updated(name, AttributeValue::Boolean(x)));
+        assert!(!replay(vec![]).was_forfeit());
+        assert!(!replay(vec![flag("TAGame.GameEvent_Team_TA:bForfeit", false)]).was_forfeit());
+        assert!(!replay(vec![flag("TAGame.GameEvent_TA:bCanVoteToForfeit", true)]).was_forfeit());
+        assert!(replay(vec![flag("TAGame.GameEvent_Team_TA:bForfeit", true)]).was_forfeit());
+    }
+
+    #[test]
+    fn active_mutators_keep_the_last_non_default_value() {
+        let frame = |replications| Frame {
+            time: 0.0,
+            delta: 0.0,
+            replications,
+        };
+        let float = |name, x| replication(1, updated(name, AttributeValue::Float(x)));
+        let content = content(
+            &[],
+            vec![
+                frame(vec![
+                    float("TAGame.Ball_TA:ReplicatedBallScale", 2.),
+                    float("Engine.WorldInfo:WorldGravityZ", -325.),
+                ]),
+                frame(vec![
+                    float("Engine.WorldInfo:WorldGravityZ", -650.),
+                    float("TAGame.Car_TA:ReplicatedCarScale", 1.),
+                    float("TAGame.CrowdActor_TA:ModifiedNoise", 3.),
+                ]),
+            ],
+        );
+        let mut expected = BTreeMap::new();
+        expected.insert(String::from("TAGame.Ball_TA:ReplicatedBallScale"), 2.);
+        assert_eq!(content.active_mutators(), expected);
+        assert_eq!(
+            attribute_value(
+                "TAGame.Ball_TA:ReplicatedBallGravityScale",
+                &bits(0.5_f32.to_bits(), 32)
+            ),
+            AttributeValue::Float(0.5)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "analysis")]
+    fn boost_amounts_follow_the_boost_component_to_its_player() {
+        let frame = |replications| Frame {
+            time: 0.0,
+            delta: 0.0,
+            replications,
+        };
+        let link = |actor, name, int| {
+            replication(
+                actor,
+                updated(name, AttributeValue::FlaggedInt { flag: true, int }),
+            )
+        };
+        let boost = |x| {
+            replication(
+                3,
+                updated(
+                    "TAGame.CarComponent_Boost_TA:ReplicatedBoostAmount",
+                    AttributeValue::Byte(x),
+                ),
+            )
+        };
+        let content = content(
+            &[],
+            vec![
+                frame(vec![boost(85)]),
+                frame(vec![
+                    link(3, "TAGame.CarComponent_TA:Vehicle", 2),
+                    link(2, "Engine.Pawn:PlayerReplicationInfo", 1),
+                ]),
+                frame(vec![boost(255)]),
+                frame(vec![boost(0)]),
+            ],
+        );
+        assert_eq!(
+            content.boost_amounts(),
+            vec![
+                BoostAmount {
+                    frame: 2,
+                    player: 1,
+                    boost: 100.,
+                },
+                BoostAmount {
+                    frame: 3,
+                    player: 1,
+                    boost: 0.,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tile_damage_is_tagged_with_the_last_team_to_touch() {
+        let stream = format!(
+            "{}1{}{}10",
+            bits(2, 8),
+            bits(4, 32),
+            vector_bits(2, 21, (3, -4, 5))
+        );
+        let damage_state = attribute_value("TAGame.BreakOutActor_Platform_TA:DamageState", &stream);
+        assert_eq!(
+            damage_state,
+            AttributeValue::DamageState {
+                tile_state: 2,
+                damaged: true,
+                offender: 4,
+                ball_location: Vector { x: 3, y: -4, z: 5 },
+                direct_hit: true,
+                immediate: false,
+            }
+        );
+        let frame = |replications| Frame {
+            time: 0.0,
+            delta: 0.0,
+            replications,
+        };
+        let damage = |tile| {
+            replication(
+                tile,
+                updated(
+                    "TAGame.BreakOutActor_Platform_TA:DamageState",
+                    damage_state.clone(),
+                ),
+            )
+        };
+        let content = content(
+            &[],
+            vec![
+                frame(vec![damage(7)]),
+                frame(vec![
+                    replication(
+                        1,
+                        updated(
+                            "TAGame.Ball_Breakout_TA:LastTeamTouch",
+                            AttributeValue::Byte(1),
+                        ),
+                    ),
+                    damage(8),
+                ]),
+            ],
+        );
+        assert_eq!(
+            content.tile_damage(),
+            vec![
+                TileDamage {
+                    frame: 0,
+                    tile: 7,
+                    state: 2,
+                    team: None,
+                },
+                TileDamage {
+                    frame: 1,
+                    tile: 8,
+                    state: 2,
+                    team: Some(1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_actor_collision_and_visibility_flags() {
+        for name in &[
+            "Engine.Actor:bBlockActors",
+            "Engine.Actor:bCollideActors",
+            "Engine.Actor:bHidden",
+        ] {
+            assert_eq!(attribute_value(name, "1"), AttributeValue::Boolean(true));
+        }
+    }
+
+    #[test]
+    fn get_attribute_value_reads_camera_angles_and_vehicle_inputs() {
+        for name in &[
+            "TAGame.CameraSettingsActor_TA:CameraPitch",
+            "TAGame.PRI_TA:CameraYaw",
+            "TAGame.Vehicle_TA:ReplicatedSteer",
+            "TAGame.Vehicle_TA:ReplicatedThrottle",
+        ] {
+            assert_eq!(
+                attribute_value(name, &bits(200, 8)),
+                AttributeValue::Byte(200)
+            );
+        }
+    }
+
+    #[test]
+    fn get_attribute_value_reads_round_and_clock_ints() {
+        assert_eq!(
+            attribute_value("TAGame.GameEvent_Soccar_TA:RoundNum", &bits(2, 32)),
+            AttributeValue::Int(2)
+        );
+        assert_eq!(
+            attribute_value(
+                "TAGame.GameEvent_Soccar_TA:SecondsRemaining",
+                &bits(299, 32)
+            ),
+            AttributeValue::Int(299)
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_boost_recharge_floats() {
+        assert_eq!(
+            attribute_value(
+                "TAGame.CarComponent_Boost_TA:RechargeDelay",
+                &bits(0.25_f32.to_bits(), 32)
+            ),
+            AttributeValue::Float(0.25)
+        );
+        assert_eq!(
+            attribute_value(
+                "TAGame.CarComponent_Boost_TA:RechargeRate",
+                &bits(16.5_f32.to_bits(), 32)
+            ),
+            AttributeValue::Float(16.5)
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_rigid_bodies_with_quaternions() {
+        let middle = bits(131_072, 18);
+        let rotation = format!("{}{}{}{}", bits(3, 2), middle, middle, middle);
+        let location = vector_bits(2, 21, (1, 2, 3));
+        let velocity = vector_bits(2, 21, (-1, 0, 1));
+
+        let stream = format!("1{}{}", location, rotation);
+        match attribute_value("TAGame.RBActor_TA:ReplicatedRBState", &stream) {
+            AttributeValue::RigidBody {
+                sleeping,
+                location,
+                rotation,
+                linear_velocity,
+                angular_velocity,
+            } => {
+                assert!(sleeping);
+                assert_eq!(location, Vector { x: 1, y: 2, z: 3 });
+                assert!(rotation.x.abs() < 1e-5);
+                assert!(rotation.y.abs() < 1e-5);
+                assert!(rotation.z.abs() < 1e-5);
+                assert!((rotation.w - 1.).abs() < 1e-5);
+                assert_eq!(linear_velocity, None);
+                assert_eq!(angular_velocity, None);
+            }
+            value => panic!("expected a rigid body but got {:?}", value),
+        }
+
+        let stream = format!("0{}{}{}{}", location, rotation, velocity, velocity);
+        match attribute_value("TAGame.RBActor_TA:ReplicatedRBState", &stream) {
+            AttributeValue::RigidBody {
+                linear_velocity,
+                angular_velocity,
+                ..
+            } => {
+                assert_eq!(linear_velocity, Some(Vector { x: -1, y: 0, z: 1 }));
+                assert_eq!(angular_velocity, Some(Vector { x: -1, y: 0, z: 1 }));
+            }
+            value => panic!("expected a rigid body but got {:?}", value),
+        }
+    }
+
+    #[test]
+    fn get_attribute_value_reads_unique_ids_for_each_platform() {
+        let bytes =
+            |bytes: &[u8]| -> String { bytes.iter().map(|&x| bits(u32::from(x), 8)).collect() };
+        let unique_id = |platform, remote: String| match attribute_value(
+            "Engine.PlayerReplicationInfo:UniqueId",
+            &format!("{}{}{}", bits(platform, 8), remote, bits(1, 8)),
+        ) {
+            AttributeValue::UniqueId(x) => {
+                assert_eq!(x.local, 1);
+                (x.platform, x.remote)
+            }
+            value => panic!("expected a unique id but got {:?}", value),
+        };
+
+        assert_eq!(
+            unique_id(0, bits(0x12_3456, 24)),
+            (Platform::Splitscreen, RemoteId::Splitscreen(0x12_3456))
+        );
+        let id = format!("{}{}", bits(0x0000_0001, 32), bits(0x0110_0000, 32));
+        assert_eq!(
+            unique_id(1, id.clone()),
+            (Platform::Steam, RemoteId::Steam(0x0110_0000_0000_0001))
+        );
+        assert_eq!(
+            unique_id(4, id),
+            (Platform::Xbox, RemoteId::Xbox(0x0110_0000_0000_0001))
+        );
+        let mut name = b"Alpha".to_vec();
+        name.resize(16, 0);
+        assert_eq!(
+            unique_id(2, format!("{}{}", bytes(&name), bytes(&[9; 16]))),
+            (
+                Platform::PlayStation,
+                RemoteId::PlayStation {
+                    name: String::from("Alpha"),
+                    code: vec![9; 16],
+                }
+            )
+        );
+        assert_eq!(
+            unique_id(6, bytes(&[3; 32])),
+            (Platform::Switch, RemoteId::Switch(vec![3; 32]))
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_loadouts_by_version() {
+        let loadout = |version, count| -> String {
+            let ids: String = (1..=count).map(|x| bits(x, 32)).collect();
+            format!("{}{}", bits(version, 8), ids)
+        };
+        let expected = Loadout {
+            version: 17,
+            body: 1,
+            decal: 2,
+            wheels: 3,
+            rocket_trail: 4,
+            antenna: 5,
+            topper: 6,
+            unknown1: 7,
+            unknown2: Some(8),
+            engine_audio: Some(9),
+            trail: Some(10),
+            goal_explosion: Some(11),
+            banner: Some(12),
+            unknown3: None,
+            unknown4: None,
+            unknown5: None,
+            unknown6: None,
+        };
+        assert_eq!(
+            attribute_value("TAGame.PRI_TA:ClientLoadout", &loadout(17, 12)),
+            AttributeValue::Loadout(expected)
+        );
+
+        let old = Loadout {
+            version: 10,
+            body: 1,
+            decal: 2,
+            wheels: 3,
+            rocket_trail: 4,
+            antenna: 5,
+            topper: 6,
+            unknown1: 7,
+            unknown2: None,
+            engine_audio: None,
+            trail: None,
+            goal_explosion: None,
+            banner: None,
+            unknown3: None,
+            unknown4: None,
+            unknown5: None,
+            unknown6: None,
+        };
+        assert_eq!(
+            attribute_value(
+                "TAGame.PRI_TA:ClientLoadouts",
+                &format!("{}{}", loadout(10, 7), loadout(10, 7))
+            ),
+            AttributeValue::Loadouts {
+                blue: old.clone(),
+                orange: old,
+            }
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_online_loadout_products() {
+        let context = context(
+            &[
+                "TAGame.ProductAttribute_Painted_TA",
+                "TAGame.ProductAttribute_UserColor_TA",
+                "TAGame.ProductAttribute_SpecialEdition_TA",
+            ],
+            &[],
+            &[],
+        );
+        let stream = format!(
+            "{}{}0{}{}1{}1{}{}0{}{}",
+            bits(2, 8),
+            bits(2, 8),
+            bits(0, 32),
+            bits(13, 31),
+            bits(1, 32),
+            bits(0x00ff_00ff, 31),
+            bits(1, 8),
+            bits(2, 32),
+            bits(3, 31)
+        );
+        let mut reader = bit_get(&stream);
+        let value = reader
+            .get_attribute_value(&context, "TAGame.PRI_TA:ClientLoadoutOnline\0")
+            .unwrap();
+        assert_eq!(reader.get_bit_index(), stream.len());
+        let product = |object_id, object: &str, value| ProductAttribute {
+            unknown: false,
+            object_id,
+            object: format!("{}\0", object),
+            value,
+        };
+        assert_eq!(
+            value,
+            AttributeValue::LoadoutOnline(vec![
+                vec![
+                    product(
+                        0,
+                        "TAGame.ProductAttribute_Painted_TA",
+                        ProductValue::Painted(13)
+                    ),
+                    ProductAttribute {
+                        unknown: true,
+                        ..product(
+                            1,
+                            "TAGame.ProductAttribute_UserColor_TA",
+                            ProductValue::UserColor(Some(0x00ff_00ff))
+                        )
+                    },
+                ],
+                vec![product(
+                    2,
+                    "TAGame.ProductAttribute_SpecialEdition_TA",
+                    ProductValue::SpecialEdition(3)
+                )],
+            ])
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_reservations_with_and_without_names() {
+        let name: String = b"Bob\0".iter().map(|&x| bits(u32::from(x), 8)).collect();
+        let stream = format!(
+            "{}{}{}{}{}{}{}10{}",
+            bits_u32c(3, 7),
+            bits(1, 8),
+            bits(42, 32),
+            bits(0, 32),
+            bits(0, 8),
+            bits(4, 32),
+            name,
+            bits(5, 6)
+        );
+        assert_eq!(
+            attribute_value("ProjectX.GRI_X:Reservations", &stream),
+            AttributeValue::Reservation {
+                number: 3,
+                unique_id: UniqueId {
+                    platform: Platform::Steam,
+                    remote: RemoteId::Steam(42),
+                    local: 0,
+                },
+                name: Some(String::from("Bob")),
+                unknown1: true,
+                unknown2: false,
+                unknown3: Some(5),
+            }
+        );
+
+        let stream = format!(
+            "{}{}{}{}01{}",
+            bits_u32c(0, 7),
+            bits(0, 8),
+            bits(0, 24),
+            bits(1, 8),
+            bits(0, 6)
+        );
+        assert_eq!(
+            attribute_value("ProjectX.GRI_X:Reservations", &stream),
+            AttributeValue::Reservation {
+                number: 0,
+                unique_id: UniqueId {
+                    platform: Platform::Splitscreen,
+                    remote: RemoteId::Splitscreen(0),
+                    local: 1,
+                },
+                name: None,
+                unknown1: false,
+                unknown2: true,
+                unknown3: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_demolitions() {
+        let stream = format!(
+            "1{}0{}{}{}",
+            bits(3, 32),
+            bits(4, 32),
+            vector_bits(2, 21, (5, -6, 7)),
+            vector_bits(0, 21, (0, 0, 0))
+        );
+        assert_eq!(
+            attribute_value("TAGame.Car_TA:ReplicatedDemolish", &stream),
+            AttributeValue::Demolish {
+                attacker: ActiveActor {
+                    active: true,
+                    actor: 3,
+                },
+                victim: ActiveActor {
+                    active: false,
+                    actor: 4,
+                },
+                attacker_velocity: Vector { x: 5, y: -6, z: 7 },
+                victim_velocity: Vector { x: 0, y: 0, z: 0 },
+            }
+        );
+    }
+
+    #[test]
+    fn parsed_replays_compare_equal_and_clone() {
+        let bytes = network_replay_bytes(1, &[0; 10]);
+        let replay = parse(&bytes).unwrap();
+        assert_eq!(replay, parse(&bytes).unwrap());
+        let copy = replay.clone();
+        assert_eq!(copy.header(), replay.header());
+        assert_eq!(copy.content(), replay.content());
+        assert_ne!(replay, parse(&network_replay_bytes(0, &[])).unwrap());
+    }
+
+    #[test]
+    fn from_slice_parses_without_copying_the_input() {
+        let bytes = replay_bytes(2);
+        let mut get = Get::from_slice(&bytes);
+        match get.bytes {
+            Cow::Borrowed(x) => assert_eq!(x.as_ptr(), bytes.as_ptr()),
+            Cow::Owned(_) => panic!("expected borrowed bytes"),
+        }
+        let replay = get.get_replay().unwrap();
+        assert_eq!(replay, Get::new(bytes.clone()).get_replay().unwrap());
+        assert_eq!(replay.header().property_int("TeamSize"), Some(2));
+    }
+
+    #[test]
+    fn parse_header_skips_a_network_stream_that_does_not_decode() {
+        let bytes = network_replay_bytes(1, &[0; 4]);
+        assert!(parse(&bytes).is_err());
+        let replay = parse_header(&bytes).unwrap();
+        assert_eq!(replay.header().property_int("NumFrames"), Some(1));
+        assert_eq!(replay.content().network_bit_length(), 32);
+        assert_eq!(replay.content().frames().count(), 0);
+    }
+
+    #[test]
+    fn header_property_lookups_check_the_type() {
+        let header = header(vec![
+            ("TeamSize", PropertyValue::Int(3)),
+            ("MapName", PropertyValue::Name(text("Stadium_P"))),
+            ("ReplayName", PropertyValue::Str(text("Final"))),
+            ("RecordFPS", PropertyValue::Float(30.)),
+        ]);
+        assert_eq!(header.property("TeamSize"), Some(&PropertyValue::Int(3)));
+        assert_eq!(header.property("TeamSize\0"), Some(&PropertyValue::Int(3)));
+        assert_eq!(header.property_int("TeamSize"), Some(3));
+        assert_eq!(header.property_int("RecordFPS"), None);
+        assert_eq!(header.property_str("MapName"), Some("Stadium_P"));
+        assert_eq!(header.property_str("ReplayName"), Some("Final"));
+        assert_eq!(header.property_str("TeamSize"), None);
+        assert_eq!(header.property_float("RecordFPS"), Some(30.));
+        assert_eq!(header.property_float("Missing"), None);
+    }
+
+    #[test]
+    fn trimmed_strips_only_one_nul_terminator() {
+        let raw = |value: &str| Text {
+            size: 0,
+            value: String::from(value),
+        };
+        assert_eq!(raw("Stadium_P\0").trimmed(), "Stadium_P");
+        assert_eq!(raw("Stadium_P").trimmed(), "Stadium_P");
+        assert_eq!(raw("\0\0\0None\0").trimmed(), "\0\0\0None");
+        assert_eq!(raw("Padded\0\0").trimmed(), "Padded\0");
+        assert_eq!(raw("Padded\0\0").as_str(), "Padded");
+    }
+
+    #[test]
+    fn get_attribute_value_reads_boost_pickups() {
+        assert_eq!(
+            attribute_value(
+                "TAGame.VehiclePickup_TA:ReplicatedPickupData",
+                &format!("1{}1", bits(8, 32))
+            ),
+            AttributeValue::Pickup {
+                instigator: Some(8),
+                picked_up: true,
+            }
+        );
+        assert_eq!(
+            attribute_value("TAGame.VehiclePickup_TA:ReplicatedPickupData", "00"),
+            AttributeValue::Pickup {
+                instigator: None,
+                picked_up: false,
+            }
+        );
+        assert_eq!(
+            attribute_value(
+                "TAGame.VehiclePickup_TA:NewReplicatedPickupData",
+                &format!("1{}{}", bits(8, 32), bits(3, 8))
+            ),
+            AttributeValue::PickupNew {
+                instigator: Some(8),
+                picked_up: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_goal_explosions() {
+        let explosion = format!("0{}{}", bits(6, 32), vector_bits(2, 21, (0, 5, -7)));
+        let actor = ActiveActor {
+            active: false,
+            actor: 6,
+        };
+        assert_eq!(
+            attribute_value("TAGame.Ball_TA:ReplicatedExplosionData", &explosion),
+            AttributeValue::Explosion {
+                actor,
+                location: Vector { x: 0, y: 5, z: -7 },
+            }
+        );
+        assert_eq!(
+            attribute_value(
+                "TAGame.Ball_TA:ReplicatedExplosionDataExtended",
+                &format!("{}1{}", explosion, bits(11, 32))
+            ),
+            AttributeValue::ExtendedExplosion {
+                actor,
+                location: Vector { x: 0, y: 5, z: -7 },
+                unknown1: true,
+                unknown2: 11,
+            }
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_actor_references() {
+        for name in &[
+            "Engine.PlayerReplicationInfo:Team",
+            "TAGame.PRI_TA:ReplicatedGameEvent",
+            "TAGame.CameraSettingsActor_TA:PRI",
+            "TAGame.Ball_TA:GameEvent",
+            "TAGame.Team_TA:GameEvent",
+        ] {
+            assert_eq!(
+                attribute_value(name, &format!("0{}", bits(21, 32))),
+                AttributeValue::FlaggedInt {
+                    flag: false,
+                    int: 21,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn get_attribute_value_reads_the_game_server_id() {
+        assert_eq!(
+            attribute_value(
+                "ProjectX.GRI_X:GameServerID",
+                &format!("{}{}", bits(0x89ab_cdef, 32), bits(0x0123_4567, 32))
+            ),
+            AttributeValue::QWord(0x0123_4567_89ab_cdef)
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_camera_settings_by_version() {
+        let float = |x: f32| bits(x.to_bits(), 32);
+        let settings = format!(
+            "{}{}{}{}{}{}",
+            float(110.),
+            float(100.),
+            float(-3.),
+            float(270.),
+            float(0.5),
+            float(4.)
+        );
+        let expected = |transition_speed| AttributeValue::CamSettings {
+            fov: 110.,
+            height: 100.,
+            angle: -3.,
+            distance: 270.,
+            stiffness: 0.5,
+            swivel_speed: 4.,
+            transition_speed,
+        };
+        assert_eq!(
+            attribute_value(
+                "TAGame.PRI_TA:CameraSettings",
+                &format!("{}{}", settings, float(1.2))
+            ),
+            expected(Some(1.2))
+        );
+
+        let mut context = context(&[], &[], &[]);
+        context.version = (868, 18, 0);
+        let mut reader = bit_get(&settings);
+        assert_eq!(
+            reader
+                .get_attribute_value(&context, "TAGame.CameraSettingsActor_TA:ProfileSettings\0")
+                .unwrap(),
+            expected(None)
+        );
+        assert_eq!(reader.get_bit_index(), settings.len());
+    }
+
+    #[test]
+    fn get_attribute_value_reads_applied_damage() {
+        let stream = format!(
+            "{}{}{}{}",
+            bits(3, 8),
+            vector_bits(2, 21, (-8, 7, 0)),
+            bits(2, 32),
+            bits(5, 32)
+        );
+        assert_eq!(
+            attribute_value("TAGame.Ball_Breakout_TA:AppliedDamage", &stream),
+            AttributeValue::AppliedDamage {
+                id: 3,
+                location: Vector { x: -8, y: 7, z: 0 },
+                damage_index: 2,
+                total_damage: 5,
+            }
+        );
+        assert_eq!(
+            attribute_value("TAGame.Ball_Breakout_TA:DamageIndex", &bits(2, 32)),
+            AttributeValue::Int(2)
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_club_colors_and_team_paint() {
+        assert_eq!(
+            attribute_value(
+                "TAGame.Team_TA:ClubColors",
+                &format!("1{}0{}", bits(12, 8), bits(34, 8))
+            ),
+            AttributeValue::ClubColors {
+                blue_flag: true,
+                blue_color: 12,
+                orange_flag: false,
+                orange_color: 34,
+            }
+        );
+        let stream = format!(
+            "{}{}{}{}{}",
+            bits(1, 8),
+            bits(5, 8),
+            bits(6, 8),
+            bits(270, 32),
+            bits(271, 32)
+        );
+        assert_eq!(
+            attribute_value("TAGame.Car_TA:TeamPaint", &stream),
+            AttributeValue::TeamPaint {
+                team: 1,
+                primary_color: 5,
+                accent_color: 6,
+                primary_finish: 270,
+                accent_finish: 271,
+            }
+        );
+    }
+
+    // The bits of a length-prefixed, NUL-terminated ASCII string.
+    fn text_bits(value: &str) -> String {
+        let bytes = format!("{}\0", value).into_bytes();
+        let size = bits(usize_u32(bytes.len()).unwrap(), 32);
+        let body: String = bytes.iter().map(|&x| bits(u32::from(x), 8)).collect();
+        format!("{}{}", size, body)
+    }
+
+    #[test]
+    fn get_attribute_value_reads_stat_titles() {
+        let stream = format!("0{}1{}{}", text_bits("Saves"), bits(14, 32), bits(2, 32));
+        assert_eq!(
+            attribute_value("TAGame.PRI_TA:RepStatTitles", &stream),
+            AttributeValue::RepStatTitle {
+                unknown: false,
+                name: String::from("Saves"),
+                flag: true,
+                object_id: 14,
+                index: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_party_leaders() {
+        assert_eq!(
+            attribute_value("TAGame.PRI_TA:PartyLeader", &bits(0, 8)),
+            AttributeValue::PartyLeader(None)
+        );
+        let stream = format!(
+            "{}{}{}{}",
+            bits(1, 8),
+            bits(99, 32),
+            bits(0, 32),
+            bits(2, 8)
+        );
+        assert_eq!(
+            attribute_value("TAGame.PRI_TA:PartyLeader", &stream),
+            AttributeValue::PartyLeader(Some(UniqueId {
+                platform: Platform::Steam,
+                remote: RemoteId::Steam(99),
+                local: 2,
+            }))
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_private_match_settings() {
+        let stream = format!(
+            "{}{}{}{}{}1",
+            text_bits("BallType=Cube"),
+            bits(2, 32),
+            bits(6, 32),
+            text_bits("Scrims"),
+            text_bits("hunter2")
+        );
+        assert_eq!(
+            attribute_value("TAGame.GameEvent_SoccarPrivate_TA:MatchSettings", &stream),
+            AttributeValue::PrivateMatchSettings {
+                mutators: String::from("BallType=Cube"),
+                joinable_by: 2,
+                max_players: 6,
+                game_name: String::from("Scrims"),
+                password: String::from("hunter2"),
+                flag: true,
+            }
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_compressed_rotations() {
+        let stream = format!(
+            "1{}01{}",
+            bits_u32c(0xffff, 0xffff),
+            bits_u32c(0x8000, 0xffff)
+        );
+        assert_eq!(
+            attribute_value("Engine.Actor:Rotation", &stream),
+            AttributeValue::Rotation {
+                pitch: Some(1.),
+                yaw: None,
+                roll: Some(0.),
+            }
+        );
+        assert_eq!(
+            attribute_value("Engine.Actor:Rotation", "000"),
+            AttributeValue::Rotation {
+                pitch: None,
+                yaw: None,
+                roll: None,
+            }
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_music_stingers() {
+        assert_eq!(
+            attribute_value(
+                "TAGame.GameEvent_Soccar_TA:ReplicatedMusicStinger",
+                &format!("1{}{}", bits(3, 32), bits(7, 8))
+            ),
+            AttributeValue::MusicStinger {
+                flag: true,
+                cue: 3,
+                trigger: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn get_attribute_value_reads_welded_info() {
+        let stream = format!(
+            "1{}{}{}01{}0",
+            bits(9, 32),
+            vector_bits(2, 21, (1, 0, -1)),
+            bits(2.5_f32.to_bits(), 32),
+            bits(0xff, 8)
+        );
+        assert_eq!(
+            attribute_value("TAGame.RBActor_TA:WeldedInfo", &stream),
+            AttributeValue::WeldedInfo {
+                actor: ActiveActor {
+                    active: true,
+                    actor: 9,
+                },
+                offset: Vector { x: 1, y: 0, z: -1 },
+                mass: 2.5,
+                rotation: Rotation {
+                    x: None,
+                    y: Some(-1),
+                    z: None,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn check_crc_32_reads_the_section_in_place() {
+        let body = [1, 2, 3, 4];
+        let get = Get::from_slice(&body);
+        assert!(get.check_crc_32(4, crc_32(&body)).is_ok());
+        match get.check_crc_32(4, 0) {
+            Err(GetError::ChecksumMismatch {
+                expected: 0,
+                actual,
+            }) => assert_eq!(actual, crc_32(&body)),
+            result => panic!("expected a checksum mismatch but got {:?}", result),
+        }
+        match get.check_crc_32(5, 0) {
+            Err(GetError::IndexOutOfBounds { index: 5, len: 4 }) => (),
+            result => panic!("expected an index out of bounds but got {:?}", result),
+        }
+        assert_eq!(get.index, 0);
+
+        let mut bytes = section_bytes(&body);
+        bytes[9] ^= 1;
+        match Get::new(bytes).get_section(Get::get_u32) {
+            Err(GetError::ChecksumMismatch { .. }) => (),
+            result => panic!("expected a checksum mismatch but got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn parse_reader_buffers_the_whole_reader() {
+        let bytes = replay_bytes(3);
+        let replay = parse_reader(std::io::Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(replay, parse(&bytes).unwrap());
+
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("broken"))
+            }
+        }
+        match parse_reader(Broken) {
+            Err(GetError::Io(_)) => (),
+            result => panic!("expected an io error but got {:?}", result.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn frame_decoder_keeps_actor_classes_after_an_unknown_attribute() {
+        let mut context = context(
+            &[
+                "TAGame.Custom_TA",
+                "Archetypes.Custom.Example",
+                "TAGame.Custom_TA:Thing",
+            ],
+            &[0],
+            &[(0, 1, 2)],
+        );
+        context.num_frames = 1;
+        let actor = bits_u32c(5, 1023);
+        let create = format!("1{}110{}{}", actor, bits(0, 32), bits(1, 32));
+        let update = format!("1{}101{}{}0", actor, bits_u32c(1, 1), bits(6, 3));
+        let stream = format!("{}{}{}{}0", bits(0, 32), bits(0, 32), create, update);
+        let mut decoder = FrameDecoder::new(bit_get(&stream).bytes, context).unwrap();
+        match decoder.decode(1) {
+            Err(BitGetError::Located { position, problem }) => {
+                assert_eq!(position.actor, Some(5));
+                match *problem {
+                    BitGetError::UnknownAttribute(ref name) => {
+                        assert_eq!(name, "TAGame.Custom_TA:Thing\0")
+                    }
+                    ref problem => panic!("expected an unknown attribute but got {:?}", problem),
+                }
+            }
+            result => panic!("expected an error but got {:?}", result.map(|_| ())),
+        }
+        assert_eq!(decoder.actor_class_id(5), Some(0));
+        assert_eq!(decoder.actor_class_id(6), None);
+    }
+
+    #[test]
+    fn get_active_actor_reads_a_flag_and_a_full_u32() {
+        let stream = format!("1{}0{}", bits(2_000, 32), bits(0xffff_ffff, 32));
+        let mut reader = bit_get(&stream);
+        assert_eq!(
+            reader.get_active_actor().unwrap(),
+            ActiveActor {
+                active: true,
+                actor: 2_000,
+            }
+        );
+        assert_eq!(reader.get_bit_index(), 33);
+        assert_eq!(
+            reader.get_active_actor().unwrap(),
+            ActiveActor {
+                active: false,
+                actor: -1,
+            }
+        );
+        assert_eq!(reader.get_bit_index(), stream.len());
+    }
+
+    #[test]
+    fn map_names_are_prettied_ignoring_case() {
+        let replay = |map: Option<&str>| Replay {
+            header: Section {
+                size: 0,
+                crc: 0,
+                value: header(
+                    map.map(|x| ("MapName", PropertyValue::Name(text(x))))
+                        .into_iter()
+                        .collect(),
+                ),
+            },
+            content: Section {
+                size: 0,
+                crc: 0,
+                value: content(&[], Vec::new()),
+            },
+            debug: None,
+        };
+        let stadium = replay(Some("Stadium_P"));
+        assert_eq!(stadium.map_name(), Some("Stadium_P"));
+        assert_eq!(stadium.pretty_map_name(), Some("DFH Stadium"));
+        assert_eq!(
+            replay(Some("EuroStadium_Night_P")).pretty_map_name(),
+            Some("Mannfield (Night)")
+        );
+        let unknown = replay(Some("Labs_Test_P"));
+        assert_eq!(unknown.map_name(), Some("Labs_Test_P"));
+        assert_eq!(unknown.pretty_map_name(), None);
+        assert_eq!(replay(None).map_name(), None);
+    }
+
+    #[test]
+    fn replay_validates_keyframes_against_the_header_frame_count() {
+        let replay = |properties| {
+            let mut content = content(&[], Vec::new());
+            content.size = 4;
+            content.keyframes = list(vec![Keyframe {
+                time: 1.0,
+                frame: 2,
+                offset: 31,
+            }]);
+            Replay {
+                header: Section {
+                    size: 0,
+                    crc: 0,
+                    value: header(properties),
+                },
+                content: Section {
+                    size: 0,
+                    crc: 0,
+                    value: content,
+                },
+                debug: None,
+            }
+        };
+        assert!(replay(vec![]).validate_keyframes().is_ok());
+        assert!(replay(vec![("NumFrames", PropertyValue::Int(3))])
+            .validate_keyframes()
+            .is_ok());
+        match replay(vec![("NumFrames", PropertyValue::Int(2))]).validate_keyframes() {
+            Err(GetError::KeyframeOutOfRange {
+                frame: 2,
+                offset: 31,
+            }) => {}
+            x => panic!("unexpected {:?}", x),
+        }
     }
 }
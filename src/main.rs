@@ -1,4 +1,7 @@
 extern crate aftershock;
+#[cfg(feature = "json")]
+#[macro_use]
+extern crate serde_json;
 
 use std::env;
 use std::fs::File;
@@ -6,11 +9,26 @@ use std::io::Read;
 use std::time::Instant;
 
 fn main() {
-    let mut bytes = Vec::new();
-    File::open(env::args().nth(1).unwrap())
-        .unwrap()
-        .read_to_end(&mut bytes)
+    let args: Vec<String> = env::args().skip(1).collect();
+    let json = args.iter().any(|arg| arg == "--json");
+    let header_json = args.iter().any(|arg| arg == "--header-json");
+    let path = args
+        .iter()
+        .find(|arg| *arg != "--json" && *arg != "--header-json")
         .unwrap();
+
+    let mut bytes = Vec::new();
+    File::open(path).unwrap().read_to_end(&mut bytes).unwrap();
+
+    // Only the header is printed, so the network stream is never decoded.
+    if header_json {
+        match aftershock::parse_header(&bytes) {
+            Err(problem) => eprintln!("{}", problem),
+            Ok(replay) => print_header(replay.header()),
+        }
+        return;
+    }
+
     let size = bytes.len();
     let size_mb = usize_f64(size) / 1_048_576.;
 
@@ -27,11 +45,122 @@ fn main() {
     );
 
     match result {
-        Err(problem) => eprintln!("{:#?}", problem),
-        Ok(replay) => println!("{:#?}", replay),
+        Err(problem) => eprintln!("{}", problem),
+        Ok(replay) => print_replay(&replay, json),
+    }
+}
+
+#[cfg(not(feature = "json"))]
+fn print_replay(replay: &aftershock::Replay, json: bool) {
+    if json {
+        eprintln!("JSON output requires the json feature.");
+    } else {
+        println!("{:#?}", replay)
+    }
+}
+
+#[cfg(not(feature = "json"))]
+fn print_header(_: &aftershock::Header) {
+    eprintln!("JSON output requires the json feature.");
+}
+
+#[cfg(feature = "json")]
+fn print_replay(replay: &aftershock::Replay, json: bool) {
+    if json {
+        println!("{}", json_replay(replay))
+    } else {
+        println!("{:#?}", replay)
     }
 }
 
+#[cfg(feature = "json")]
+fn print_header(header: &aftershock::Header) {
+    println!("{}", json!({ "header": { "body": json_header(header) } }))
+}
+
+// The JSON follows Rattletrap's schema with a few deviations. Sections do not
+// include their size or CRC, frames do not include their replications, and
+// properties of unknown types are emitted as raw bytes.
+#[cfg(feature = "json")]
+fn json_replay(replay: &aftershock::Replay) -> serde_json::Value {
+    json!({
+        "header": {
+            "body": json_header(replay.header()),
+        },
+        "content": {
+            "body": {
+                "frames": replay.frames().map(json_frame).collect::<Vec<_>>(),
+            },
+        },
+    })
+}
+
+#[cfg(feature = "json")]
+fn json_header(header: &aftershock::Header) -> serde_json::Value {
+    let version = header.version();
+    json!({
+        "engine_version": version.major(),
+        "licensee_version": version.minor(),
+        "patch_version": version.patch(),
+        "label": header.label(),
+        "properties": json_properties(header.properties()),
+    })
+}
+
+#[cfg(feature = "json")]
+fn json_properties(properties: &aftershock::Dictionary<aftershock::Property>) -> serde_json::Value {
+    let elements: Vec<_> = properties
+        .iter()
+        .map(|(key, property)| json!([key, json_property(property)]))
+        .collect();
+    json!({
+        "elements": elements,
+        "last_key": properties.last(),
+    })
+}
+
+#[cfg(feature = "json")]
+fn json_property(property: &aftershock::Property) -> serde_json::Value {
+    json!({
+        "kind": property.label(),
+        "size": property.size(),
+        "value": json_property_value(property.value()),
+    })
+}
+
+#[cfg(feature = "json")]
+fn json_property_value(value: &aftershock::PropertyValue) -> serde_json::Value {
+    use aftershock::PropertyValue;
+    match value {
+        PropertyValue::Array(x) => {
+            json!({ "array": x.value().iter().map(json_properties).collect::<Vec<_>>() })
+        }
+        PropertyValue::Bool(x) => json!({ "bool": x }),
+        PropertyValue::Byte { key, value } => {
+            json!({ "byte": [key.as_str(), value.as_ref().map(|x| x.as_str())] })
+        }
+        PropertyValue::Float(x) => json!({ "float": x }),
+        PropertyValue::Int(x) => json!({ "int": u32_i32(*x) }),
+        PropertyValue::Name(x) => json!({ "name": x.as_str() }),
+        PropertyValue::QWord(x) => json!({ "q_word": x.to_string() }),
+        PropertyValue::Str(x) => json!({ "str": x.as_str() }),
+        PropertyValue::Unknown { bytes, .. } => json!({ "unknown": bytes }),
+    }
+}
+
+#[cfg(feature = "json")]
+fn json_frame(frame: &aftershock::Frame) -> serde_json::Value {
+    json!({
+        "time": frame.time(),
+        "delta": frame.delta(),
+    })
+}
+
+#[cfg(feature = "json")]
+fn u32_i32(x: u32) -> i32 {
+    x as i32
+}
+
 fn u32_u64(x: u32) -> u64 {
     u64::from(x)
 }
@@ -0,0 +1,63 @@
+#![cfg(feature = "json")]
+
+extern crate aftershock;
+extern crate serde_json;
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+fn text(value: &str) -> Vec<u8> {
+    let mut bytes = (value.len() as i32 + 1).to_le_bytes().to_vec();
+    bytes.extend_from_slice(value.as_bytes());
+    bytes.push(0);
+    bytes
+}
+
+fn section(body: &[u8]) -> Vec<u8> {
+    let mut crc = aftershock::Crc32::new();
+    crc.update(body);
+    let mut bytes = (body.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(&crc.finalize().to_le_bytes());
+    bytes.extend_from_slice(body);
+    bytes
+}
+
+fn replay() -> Vec<u8> {
+    let mut header = Vec::new();
+    for x in &[868u32, 22, 7] {
+        header.extend_from_slice(&x.to_le_bytes());
+    }
+    header.extend(text("TAGame.Replay_Soccar_TA"));
+    header.extend(text("TeamSize"));
+    header.extend(text("IntProperty"));
+    header.extend_from_slice(&4u64.to_le_bytes());
+    header.extend_from_slice(&3u32.to_le_bytes());
+    header.extend(text("None"));
+    // Every list in the content is empty, and so is the network stream.
+    let content = vec![0; 40];
+    let mut bytes = section(&header);
+    bytes.extend(section(&content));
+    bytes
+}
+
+#[test]
+fn header_json_prints_only_the_header() {
+    let path = env::temp_dir().join(format!("aftershock-{}.replay", std::process::id()));
+    fs::write(&path, replay()).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aftershock"))
+        .arg("--header-json")
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let body = &json["header"]["body"];
+    assert_eq!(body["engine_version"], 868);
+    assert_eq!(body["label"], "TAGame.Replay_Soccar_TA");
+    assert_eq!(body["properties"]["elements"][0][0], "TeamSize");
+    assert_eq!(body["properties"]["elements"][0][1]["value"]["int"], 3);
+    assert!(json.get("content").is_none());
+}